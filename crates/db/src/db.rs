@@ -14,6 +14,7 @@ pub use util::channel::{RELEASE_CHANNEL, RELEASE_CHANNEL_NAME};
 pub use util::paths::DB_DIR;
 
 use sqlez::domain::Migrator;
+use sqlez::migrations::NewerDatabaseError;
 use sqlez::thread_safe_connection::ThreadSafeConnection;
 use sqlez_macros::sql;
 use std::future::Future;
@@ -40,12 +41,14 @@ const DB_FILE_NAME: &'static str = "db.sqlite";
 lazy_static::lazy_static! {
     pub static ref ZED_STATELESS: bool = std::env::var("ZED_STATELESS").map_or(false, |v| !v.is_empty());
     pub static ref ALL_FILE_DB_FAILED: AtomicBool = AtomicBool::new(false);
+    pub static ref DB_WRITTEN_BY_NEWER_VERSION: AtomicBool = AtomicBool::new(false);
 }
 
 /// Open or create a database at the given directory path.
 /// This will retry a couple times if there are failures. If opening fails once, the db directory
 /// is moved to a backup folder and a new one is created. If that fails, a shared in memory db is created.
-/// In either case, static variables are set so that the user can be notified.
+/// In either case, static variables are set so that the user can be notified. A db written by a
+/// newer version is left untouched, and flagged separately so the user knows why nothing is saved.
 pub async fn open_db<M: Migrator + 'static>(
     db_dir: &Path,
     release_channel: &ReleaseChannel,
@@ -60,32 +63,43 @@ pub async fn open_db<M: Migrator + 'static>(
     let connection = async_maybe!({
         smol::fs::create_dir_all(&main_db_dir)
             .await
-            .context("Could not create db directory")
-            .log_err()?;
+            .context("Could not create db directory")?;
         let db_path = main_db_dir.join(Path::new(DB_FILE_NAME));
         open_main_db(&db_path).await
     })
     .await;
 
-    if let Some(connection) = connection {
-        return connection;
+    match connection {
+        Ok(connection) => return connection,
+        Err(error) => {
+            log::error!("{error:?}");
+            if error.downcast_ref::<NewerDatabaseError>().is_some() {
+                DB_WRITTEN_BY_NEWER_VERSION.store(true, Ordering::Release);
+            } else {
+                // Set another static ref so that we can escalate the notification
+                ALL_FILE_DB_FAILED.store(true, Ordering::Release);
+            }
+        }
     }
 
-    // Set another static ref so that we can escalate the notification
-    ALL_FILE_DB_FAILED.store(true, Ordering::Release);
-
     // If still failed, create an in memory db with a known name
     open_fallback_db().await
 }
 
-async fn open_main_db<M: Migrator>(db_path: &PathBuf) -> Option<ThreadSafeConnection<M>> {
+async fn open_main_db<M: Migrator>(db_path: &PathBuf) -> anyhow::Result<ThreadSafeConnection<M>> {
     log::info!("Opening main db");
+    open_file_db(db_path).await
+}
+
+/// Open or create a persistent database file at exactly the given path, in WAL mode with
+/// foreign keys enforced, and run the domain's migrations on it. Unlike `open_db` this
+/// neither retries nor falls back to memory, so failures are returned to the caller.
+pub async fn open_file_db<M: Migrator>(db_path: &Path) -> anyhow::Result<ThreadSafeConnection<M>> {
     ThreadSafeConnection::<M>::builder(db_path.to_string_lossy().as_ref(), true)
         .with_db_initialization_query(DB_INITIALIZE_QUERY)
         .with_connection_initialize_query(CONNECTION_INITIALIZE_QUERY)
         .build()
         .await
-        .log_err()
 }
 
 async fn open_fallback_db<M: Migrator>() -> ThreadSafeConnection<M> {
@@ -192,12 +206,13 @@ where
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::Ordering;
     use std::thread;
 
     use sqlez::domain::Domain;
     use sqlez_macros::sql;
 
-    use crate::open_db;
+    use crate::{open_db, DB_WRITTEN_BY_NEWER_VERSION};
 
     // Test bad migration panics
     #[gpui::test]
@@ -273,6 +288,64 @@ mod tests {
         );
     }
 
+    /// Test that a DB written by a newer version is flagged and left as it was
+    #[gpui::test]
+    async fn test_db_written_by_newer_version(cx: &mut gpui::TestAppContext) {
+        cx.executor().allow_parking();
+
+        enum NewerDB {}
+
+        impl Domain for NewerDB {
+            fn name() -> &'static str {
+                "db_newer_version_tests"
+            }
+
+            fn migrations() -> &'static [&'static str] {
+                &[
+                    sql!(CREATE TABLE test(value);),
+                    sql!(CREATE TABLE test2(value);),
+                ]
+            }
+        }
+
+        enum OlderDB {}
+
+        impl Domain for OlderDB {
+            fn name() -> &'static str {
+                "db_newer_version_tests"
+            }
+
+            fn migrations() -> &'static [&'static str] {
+                &[sql!(CREATE TABLE test(value);)]
+            }
+        }
+
+        let tempdir = tempfile::Builder::new()
+            .prefix("DbTests")
+            .tempdir()
+            .unwrap();
+        {
+            let newer_db =
+                open_db::<NewerDB>(tempdir.path(), &util::channel::ReleaseChannel::Dev).await;
+            assert!(newer_db.persistent());
+        }
+
+        let older_db =
+            open_db::<OlderDB>(tempdir.path(), &util::channel::ReleaseChannel::Dev).await;
+        assert!(!older_db.persistent());
+        assert!(DB_WRITTEN_BY_NEWER_VERSION.load(Ordering::Acquire));
+        drop(older_db);
+
+        let newer_db =
+            open_db::<NewerDB>(tempdir.path(), &util::channel::ReleaseChannel::Dev).await;
+        assert!(newer_db.persistent());
+        assert!(
+            newer_db.select_row::<usize>("SELECT * FROM test2").unwrap()()
+                .unwrap()
+                .is_none()
+        );
+    }
+
     /// Test that DB exists but corrupted (causing recreate)
     #[gpui::test(iterations = 30)]
     async fn test_simultaneous_db_corruption(cx: &mut gpui::TestAppContext) {
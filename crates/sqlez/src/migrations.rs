@@ -2,9 +2,12 @@
 // effected tables, actual query text, and order.
 // If a migration is run and any of the query texts don't match, the app panics on startup (maybe fallback
 // to creating a new db?)
+// If more migrations were run than the domain knows about, the db was written by a newer
+// version and opening it fails.
 // Otherwise any missing migrations are run on the connection
 
 use std::ffi::CString;
+use std::fmt;
 
 use anyhow::{anyhow, Context, Result};
 use indoc::{formatdoc, indoc};
@@ -12,6 +15,28 @@ use libsqlite3_sys::sqlite3_exec;
 
 use crate::connection::Connection;
 
+/// Returned from `migrate` when the database has more completed migrations for a domain than
+/// the running version knows about, meaning it was written by a newer version.
+#[derive(Debug)]
+pub struct NewerDatabaseError {
+    pub domain: &'static str,
+    pub completed_migrations: usize,
+    pub known_migrations: usize,
+}
+
+impl fmt::Display for NewerDatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Database for {} has {} completed migrations, but only {} are known. \
+            It was likely written by a newer version",
+            self.domain, self.completed_migrations, self.known_migrations
+        )
+    }
+}
+
+impl std::error::Error for NewerDatabaseError {}
+
 impl Connection {
     fn eager_exec(&self, sql: &str) -> anyhow::Result<()> {
         let sql_str = CString::new(sql).context("Error creating cstr")?;
@@ -51,6 +76,15 @@ impl Connection {
                     ORDER BY step
                     "})?(domain)?;
 
+            if completed_migrations.len() > migrations.len() {
+                return Err(NewerDatabaseError {
+                    domain,
+                    completed_migrations: completed_migrations.len(),
+                    known_migrations: migrations.len(),
+                }
+                .into());
+            }
+
             let mut store_completed_migration = self
                 .exec_bound("INSERT INTO migrations (domain, step, migration) VALUES (?, ?, ?)")?;
 
@@ -84,7 +118,7 @@ impl Connection {
 mod test {
     use indoc::indoc;
 
-    use crate::connection::Connection;
+    use crate::{connection::Connection, migrations::NewerDatabaseError};
 
     #[test]
     fn test_migrations_are_added_to_table() {
@@ -282,6 +316,39 @@ mod test {
         assert!(second_migration_result.is_err())
     }
 
+    #[test]
+    fn older_database_is_upgraded_in_place() {
+        let connection = Connection::open_memory(Some("older_database_is_upgraded_in_place"));
+
+        let first_step = "CREATE TABLE test (col INTEGER)";
+        let second_step = "ALTER TABLE test ADD COLUMN other TEXT";
+
+        connection.migrate("test", &[first_step]).unwrap();
+        connection
+            .exec("INSERT INTO test (col) VALUES (1)")
+            .unwrap()()
+        .unwrap();
+
+        // Only the new step runs, and existing rows survive it
+        connection
+            .migrate("test", &[first_step, second_step])
+            .unwrap();
+        assert_eq!(
+            connection
+                .select_row::<(usize, Option<String>)>("SELECT col, other FROM test")
+                .unwrap()()
+            .unwrap(),
+            Some((1, None))
+        );
+
+        // A version that only knows about the first step can't open the upgraded database
+        let error = connection.migrate("test", &[first_step]).unwrap_err();
+        assert!(
+            error.downcast_ref::<NewerDatabaseError>().is_some(),
+            "{error}"
+        );
+    }
+
     #[test]
     fn test_create_alter_drop() {
         let connection = Connection::open_memory(Some("test_create_alter_drop"));
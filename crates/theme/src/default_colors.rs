@@ -2,7 +2,7 @@ use gpui::{Hsla, Rgba};
 
 use crate::scale::{ColorScaleSet, ColorScales};
 use crate::ColorScale;
-use crate::{SystemColors, ThemeColors};
+use crate::{StatusBoxCorner, SystemColors, ThemeColors};
 
 pub(crate) fn neutral() -> ColorScaleSet {
     sand()
@@ -58,6 +58,9 @@ impl ThemeColors {
             panel_background: neutral().light().step_2(),
             panel_focused_border: blue().light().step_5(),
             pane_focused_border: blue().light().step_5(),
+            horizontal_pane_divider: neutral().light().step_6(),
+            vertical_pane_divider: neutral().light().step_6(),
+            leader_status_corner: StatusBoxCorner::BottomRight,
             scrollbar_thumb_background: neutral().light_alpha().step_3(),
             scrollbar_thumb_hover_background: neutral().light_alpha().step_4(),
             scrollbar_thumb_border: gpui::transparent_black(),
@@ -152,6 +155,9 @@ impl ThemeColors {
             panel_background: neutral().dark().step_2(),
             panel_focused_border: blue().dark().step_5(),
             pane_focused_border: blue().dark().step_5(),
+            horizontal_pane_divider: neutral().dark().step_6(),
+            vertical_pane_divider: neutral().dark().step_6(),
+            leader_status_corner: StatusBoxCorner::BottomRight,
             scrollbar_thumb_background: neutral().dark_alpha().step_3(),
             scrollbar_thumb_hover_background: neutral().dark_alpha().step_4(),
             scrollbar_thumb_border: gpui::transparent_black(),
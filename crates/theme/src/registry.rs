@@ -48,6 +48,12 @@ impl ThemeRegistry {
                 Appearance::Dark => ThemeColors::dark(),
             };
             theme_colors.refine(&user_theme.styles.colors);
+            if user_theme.styles.colors.horizontal_pane_divider.is_none() {
+                theme_colors.horizontal_pane_divider = theme_colors.border;
+            }
+            if user_theme.styles.colors.vertical_pane_divider.is_none() {
+                theme_colors.vertical_pane_divider = theme_colors.border;
+            }
 
             let mut status_colors = match user_theme.appearance {
                 Appearance::Light => StatusColors::light(),
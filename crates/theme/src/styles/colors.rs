@@ -1,5 +1,6 @@
-use gpui::Hsla;
+use gpui::{Hsla, Styled};
 use refineable::Refineable;
+use serde::Deserialize;
 use std::sync::Arc;
 
 use crate::{PlayerColors, StatusColors, StatusColorsRefinement, SyntaxTheme, SystemColors};
@@ -119,6 +120,19 @@ pub struct ThemeColors {
     pub panel_background: Hsla,
     pub panel_focused_border: Hsla,
     pub pane_focused_border: Hsla,
+    /// The color of the dividers between panes laid out side by side.
+    ///
+    /// Falls back to `border` if the theme doesn't set it.
+    pub horizontal_pane_divider: Hsla,
+    /// The color of the dividers between stacked panes.
+    ///
+    /// Falls back to `border` if the theme doesn't set it.
+    pub vertical_pane_divider: Hsla,
+    /// Which corner of a followed pane shows where the leader is, when they're somewhere
+    /// that can't be followed.
+    ///
+    /// Falls back to the bottom right corner if the theme doesn't set it.
+    pub leader_status_corner: StatusBoxCorner,
     /// The color of the scrollbar thumb.
     pub scrollbar_thumb_background: Hsla,
     /// The color of the scrollbar thumb when hovered over.
@@ -233,6 +247,28 @@ pub struct ThemeColors {
     pub link_text_hover: Hsla,
 }
 
+#[derive(Copy, Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusBoxCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+impl StatusBoxCorner {
+    /// Insets an absolutely positioned `element` from this corner of its parent.
+    pub fn position<E: Styled>(self, element: E) -> E {
+        match self {
+            StatusBoxCorner::TopLeft => element.top_3().left_3(),
+            StatusBoxCorner::TopRight => element.top_3().right_3(),
+            StatusBoxCorner::BottomLeft => element.bottom_3().left_3(),
+            StatusBoxCorner::BottomRight => element.bottom_3().right_3(),
+        }
+    }
+}
+
 #[derive(Refineable, Clone)]
 pub struct ThemeStyles {
     pub system: SystemColors,
@@ -308,4 +344,18 @@ mod tests {
         assert_eq!(colors.background, Some(gpui::rgb(0xff00ff).into()));
         assert_eq!(colors.text, Some(gpui::rgb(0xff0000).into()));
     }
+
+    #[test]
+    fn override_leader_status_corner() {
+        let mut colors = ThemeColors::dark();
+        assert_eq!(colors.leader_status_corner, StatusBoxCorner::BottomRight);
+
+        let overrides: ThemeColorsRefinement = serde_json::from_value(json!({
+            "leader_status_corner": "top_left"
+        }))
+        .unwrap();
+        colors.refine(&overrides);
+
+        assert_eq!(colors.leader_status_corner, StatusBoxCorner::TopLeft);
+    }
 }
@@ -226,6 +226,9 @@ impl Zed1ThemeConverter {
             panel_background: convert(middle.base.default.background),
             panel_focused_border: convert(lowest.accent.hovered.border),
             pane_focused_border: convert(lowest.accent.hovered.border),
+            horizontal_pane_divider: convert(lowest.base.default.border),
+            vertical_pane_divider: convert(lowest.base.default.border),
+            leader_status_corner: None,
             scrollbar_thumb_background: convert(middle.base.inverted.background)
                 .map(|color| color_alpha(color, 0.3)),
             scrollbar_thumb_hover_background: convert(middle.base.hovered.background),
@@ -426,6 +426,9 @@ impl Dock {
                 if serialized.visible {
                     self.set_open(true, cx);
                 }
+                if let Some(size) = serialized.size {
+                    self.resize_active_panel(Some(px(size)), cx);
+                }
                 if serialized.zoom {
                     if let Some(panel) = self.active_panel() {
                         panel.set_zoomed(true, cx)
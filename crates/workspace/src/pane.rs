@@ -11,7 +11,7 @@ use gpui::{
     actions, impl_actions, overlay, prelude::*, Action, AnchorCorner, AnyElement, AppContext,
     AsyncWindowContext, DismissEvent, Div, DragMoveEvent, EntityId, EventEmitter, ExternalPaths,
     FocusHandle, FocusableView, Model, MouseButton, NavigationDirection, Pixels, Point,
-    PromptLevel, Render, ScrollHandle, Subscription, Task, View, ViewContext, VisualContext,
+    PromptLevel, Render, ScrollHandle, Size, Subscription, Task, View, ViewContext, VisualContext,
     WeakView, WindowContext,
 };
 use parking_lot::Mutex;
@@ -175,6 +175,7 @@ pub struct Pane {
     _subscriptions: Vec<Subscription>,
     tab_bar_scroll_handle: ScrollHandle,
     display_nav_history_buttons: bool,
+    min_size: Size<Pixels>,
 }
 
 pub struct ItemNavHistory {
@@ -302,11 +303,25 @@ impl Pane {
                             .icon_size(IconSize::Small)
                             .icon_color(Color::Muted)
                             .on_click(cx.listener(|pane, _, cx| {
+                                let split_actions = [
+                                    (
+                                        SplitDirection::Right,
+                                        "Split Right",
+                                        SplitRight.boxed_clone(),
+                                    ),
+                                    (SplitDirection::Left, "Split Left", SplitLeft.boxed_clone()),
+                                    (SplitDirection::Up, "Split Up", SplitUp.boxed_clone()),
+                                    (SplitDirection::Down, "Split Down", SplitDown.boxed_clone()),
+                                ]
+                                .into_iter()
+                                .filter(|(direction, _, _)| pane.can_split_in(*direction, cx))
+                                .collect::<Vec<_>>();
                                 let menu = ContextMenu::build(cx, |menu, _| {
-                                    menu.action("Split Right", SplitRight.boxed_clone())
-                                        .action("Split Left", SplitLeft.boxed_clone())
-                                        .action("Split Up", SplitUp.boxed_clone())
-                                        .action("Split Down", SplitDown.boxed_clone())
+                                    split_actions
+                                        .into_iter()
+                                        .fold(menu, |menu, (_, label, action)| {
+                                            menu.action(label, action)
+                                        })
                                 });
                                 cx.subscribe(&menu, |pane, _, _: &DismissEvent, cx| {
                                     pane.focus(cx);
@@ -337,6 +352,7 @@ impl Pane {
                     .into_any_element()
             }),
             display_nav_history_buttons: true,
+            min_size: Size::default(),
             _subscriptions: subscriptions,
         }
     }
@@ -410,6 +426,17 @@ impl Pane {
         cx.notify();
     }
 
+    /// The smallest size this pane's contents can be laid out at. Splits won't squeeze or
+    /// drag the pane below it, or below the `pane_min_width`/`pane_min_height` settings.
+    pub fn min_size(&self) -> Size<Pixels> {
+        self.min_size
+    }
+
+    pub fn set_min_size(&mut self, min_size: Size<Pixels>, cx: &mut ViewContext<Self>) {
+        self.min_size = min_size;
+        cx.notify();
+    }
+
     pub fn set_can_navigate(&mut self, can_navigate: bool, cx: &mut ViewContext<Self>) {
         self.toolbar.update(cx, |toolbar, cx| {
             toolbar.set_can_navigate(can_navigate, cx);
@@ -1219,6 +1246,17 @@ impl Pane {
         cx.emit(Event::Split(direction));
     }
 
+    /// Whether the workspace has room to split this pane in `direction`, see
+    /// [`PaneGroup::can_split`](crate::PaneGroup::can_split).
+    fn can_split_in(&self, direction: SplitDirection, cx: &ViewContext<Self>) -> bool {
+        self.workspace.upgrade().map_or(true, |workspace| {
+            workspace
+                .read(cx)
+                .center
+                .can_split(cx.view(), direction, cx)
+        })
+    }
+
     pub fn toolbar(&self) -> &View<Toolbar> {
         &self.toolbar
     }
@@ -1582,19 +1620,7 @@ impl Pane {
             return;
         }
 
-        let edge_width = cx.rem_size() * 8;
-        let cursor = event.event.position;
-        let direction = if cursor.x < event.bounds.left() + edge_width {
-            Some(SplitDirection::Left)
-        } else if cursor.x > event.bounds.right() - edge_width {
-            Some(SplitDirection::Right)
-        } else if cursor.y < event.bounds.top() + edge_width {
-            Some(SplitDirection::Up)
-        } else if cursor.y > event.bounds.bottom() - edge_width {
-            Some(SplitDirection::Down)
-        } else {
-            None
-        };
+        let direction = SplitDirection::for_drop_position(event.bounds, event.event.position);
 
         if direction != self.drag_split_direction {
             self.drag_split_direction = direction;
@@ -1620,6 +1646,16 @@ impl Pane {
             .update(cx, |_, cx| {
                 cx.defer(move |workspace, cx| {
                     if let Some(split_direction) = split_direction {
+                        // Dropping a pane's only tab on an edge moves the pane itself, unless
+                        // it's outside the center, such as in a dock
+                        if from_pane != to_pane
+                            && from_pane.read(cx).items_len() == 1
+                            && workspace
+                                .relocate_pane(&from_pane, &to_pane, split_direction, cx)
+                                .unwrap_or(false)
+                        {
+                            return;
+                        }
                         to_pane = workspace.split_pane(to_pane, split_direction, cx);
                     }
                     workspace.move_item(from_pane, to_pane, item_id, ix, cx);
@@ -2521,6 +2557,60 @@ mod tests {
         assert_item_labels(&pane, [], cx);
     }
 
+    #[gpui::test]
+    async fn test_drop_only_tab_on_edge(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let a = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        let b = workspace.update(cx, |workspace, cx| {
+            workspace.split_pane(a.clone(), SplitDirection::Right, cx)
+        });
+        let c = workspace.update(cx, |workspace, cx| {
+            workspace.split_pane(b.clone(), SplitDirection::Right, cx)
+        });
+        let item = add_labeled_item(&a, "A", false, cx);
+        add_labeled_item(&b, "B1", false, cx);
+        add_labeled_item(&b, "B2", false, cx);
+        add_labeled_item(&c, "C", false, cx);
+
+        let drop_tab = |from: &View<Pane>,
+                        item: Box<dyn ItemHandle>,
+                        onto: &View<Pane>,
+                        cx: &mut VisualTestContext| {
+            let dragged_tab = DraggedTab {
+                pane: from.clone(),
+                item,
+                ix: 0,
+                detail: 0,
+                is_active: true,
+            };
+            onto.update(cx, |pane, cx| {
+                pane.drag_split_direction = Some(SplitDirection::Down);
+                pane.handle_tab_drop(&dragged_tab, 0, cx);
+            });
+        };
+
+        // The last tab of `a` takes `a` along with it, below `c`.
+        drop_tab(&a, item, &c, cx);
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, _| {
+            assert_eq!(workspace.center.panes(), [&b, &c, &a]);
+        });
+        assert_item_labels(&a, ["A*"], cx);
+
+        // `b` has another tab, so the dropped one goes into a new pane.
+        let b1 = b.update(cx, |pane, _| pane.items().next().unwrap().boxed_clone());
+        drop_tab(&b, b1, &a, cx);
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, _| {
+            assert_eq!(workspace.center.panes().len(), 4)
+        });
+        assert_item_labels(&b, ["B2*"], cx);
+    }
+
     fn init_test(cx: &mut TestAppContext) {
         cx.update(|cx| {
             let settings_store = SettingsStore::test(cx);
@@ -1,34 +1,76 @@
-use crate::{pane_group::element::pane_axis, AppState, FollowerState, Pane, Workspace};
+use crate::{
+    pane_group::element::pane_axis,
+    persistence::{
+        model::{SerializedPane, SerializedPaneGroup},
+        SerializedAxis,
+    },
+    AppState, FollowerState, Pane, Workspace, WorkspaceSettings,
+};
 use anyhow::{anyhow, Result};
 use call::{ActiveCall, ParticipantLocation};
-use collections::HashMap;
+use collections::{HashMap, HashSet};
 use gpui::{
-    point, size, AnyView, AnyWeakView, Axis, Bounds, IntoElement, Model, MouseButton, Pixels,
-    Point, View, ViewContext,
+    point, size, Along, AnyView, AnyWeakView, AppContext, Axis, Bounds, FocusHandle, IntoElement,
+    Model, MouseButton, Pixels, Point, Subscription, View, ViewContext,
 };
 use parking_lot::Mutex;
 use project::Project;
 use serde::Deserialize;
-use std::sync::Arc;
-use ui::prelude::*;
+use settings::Settings;
+use std::{sync::Arc, time::Instant};
+use ui::{prelude::*, ContextMenu};
 
 pub const HANDLE_HITBOX_SIZE: f32 = 4.0;
-const HORIZONTAL_MIN_SIZE: f32 = 80.;
-const VERTICAL_MIN_SIZE: f32 = 100.;
+const MIN_SPLIT_RATIO: f32 = 0.1;
+const MAX_SPLIT_RATIO: f32 = 0.9;
 
+/// A tree of panes split along alternating axes. A group always contains at least one
+/// pane, which the rest of the workspace relies on, so removing the last pane is a no-op.
 #[derive(Clone)]
 pub struct PaneGroup {
     pub(crate) root: Member,
+    max_panes: Option<usize>,
+    /// The flex each minimized pane had before it was minimized.
+    minimized: HashMap<View<Pane>, f32>,
+    /// Panes that [`Self::remove`] leaves in place.
+    locked: HashSet<View<Pane>>,
 }
 
 impl PaneGroup {
     pub(crate) fn with_root(root: Member) -> Self {
-        Self { root }
+        Self {
+            root,
+            max_panes: None,
+            minimized: HashMap::default(),
+            locked: HashSet::default(),
+        }
     }
 
     pub fn new(pane: View<Pane>) -> Self {
-        Self {
-            root: Member::Pane(pane),
+        Self::with_root(Member::Pane(pane))
+    }
+
+    /// Builds a group whose root axis holds `members` with equal flexes, or whose root is
+    /// the member itself when there's only one. Fails when `members` is empty, as a group
+    /// can't exist without a pane.
+    pub fn from_members(members: Vec<Member>, axis: Axis) -> Result<Self> {
+        if members.is_empty() {
+            return Err(anyhow!("A pane group needs at least one pane"));
+        }
+        Ok(Self::with_root(Member::from_members(axis, members)))
+    }
+
+    /// Limits how many panes splitting can produce. Groups are unlimited by default.
+    pub fn set_max_panes(&mut self, max_panes: Option<usize>) {
+        self.max_panes = max_panes;
+    }
+
+    fn check_pane_capacity(&self, new_pane_count: usize) -> Result<()> {
+        match self.max_panes {
+            Some(max_panes) if self.len() + new_pane_count > max_panes => {
+                Err(anyhow!("Maximum pane count reached"))
+            }
+            _ => Ok(()),
         }
     }
 
@@ -38,16 +80,93 @@ impl PaneGroup {
         new_pane: &View<Pane>,
         direction: SplitDirection,
     ) -> Result<()> {
+        self.split_internal(old_pane, new_pane, direction, None)
+            .map(|_| ())
+    }
+
+    /// Like [`Self::split`], but reports whether `new_pane` joined the axis around
+    /// `old_pane` or the two were wrapped in a new axis.
+    pub fn split_returning_axis(
+        &mut self,
+        old_pane: &View<Pane>,
+        new_pane: &View<Pane>,
+        direction: SplitDirection,
+    ) -> Result<SplitOutcome> {
+        self.split_internal(old_pane, new_pane, direction, None)
+    }
+
+    /// Splits `old_pane`, giving `ratio` of the space it currently occupies to
+    /// `new_pane`. Unlike [`Self::split`], only the two affected flexes change, so
+    /// sibling panes keep their sizes.
+    ///
+    /// `ratio` is clamped to `0.1..=0.9` so that neither pane collapses entirely.
+    pub fn split_with_ratio(
+        &mut self,
+        old_pane: &View<Pane>,
+        new_pane: &View<Pane>,
+        direction: SplitDirection,
+        ratio: f32,
+    ) -> Result<()> {
+        let ratio = ratio.clamp(MIN_SPLIT_RATIO, MAX_SPLIT_RATIO);
+        self.split_internal(old_pane, new_pane, direction, Some(ratio))
+            .map(|_| ())
+    }
+
+    /// Replaces `anchor` with an evenly spaced grid of `rows` by `cols` panes: a vertical
+    /// axis of horizontal rows, filled in row-major order starting with `anchor` itself.
+    pub fn split_grid(
+        &mut self,
+        anchor: &View<Pane>,
+        new_panes: Vec<View<Pane>>,
+        rows: usize,
+        cols: usize,
+    ) -> Result<()> {
+        if rows == 0 || cols == 0 || new_panes.len() + 1 != rows * cols {
+            return Err(anyhow!(
+                "A {rows}x{cols} grid needs {} new panes, got {}",
+                (rows * cols).saturating_sub(1),
+                new_panes.len()
+            ));
+        }
+        self.check_pane_capacity(new_panes.len())?;
+
+        let member = self
+            .root
+            .pane_member_mut(anchor)
+            .ok_or_else(|| anyhow!("Pane not found"))?;
+
+        let mut panes = std::iter::once(anchor.clone())
+            .chain(new_panes)
+            .map(Member::Pane);
+        let rows = (0..rows)
+            .map(|_| Member::from_members(Axis::Horizontal, panes.by_ref().take(cols).collect()))
+            .collect();
+        *member = Member::from_members(Axis::Vertical, rows);
+        Ok(())
+    }
+
+    fn split_internal(
+        &mut self,
+        old_pane: &View<Pane>,
+        new_pane: &View<Pane>,
+        direction: SplitDirection,
+        ratio: Option<f32>,
+    ) -> Result<SplitOutcome> {
+        if self.root.contains(new_pane) {
+            return Err(anyhow!("Pane already present"));
+        }
+        self.check_pane_capacity(1)?;
         match &mut self.root {
             Member::Pane(pane) => {
                 if pane == old_pane {
-                    self.root = Member::new_axis(old_pane.clone(), new_pane.clone(), direction);
-                    Ok(())
+                    self.root =
+                        Member::new_axis(old_pane.clone(), new_pane.clone(), direction, ratio);
+                    Ok(SplitOutcome::CreatedAxis)
                 } else {
                     Err(anyhow!("Pane not found"))
                 }
             }
-            Member::Axis(axis) => axis.split(old_pane, new_pane, direction),
+            Member::Axis(axis) => axis.split(old_pane, new_pane, direction, ratio),
         }
     }
 
@@ -58,6 +177,33 @@ impl PaneGroup {
         }
     }
 
+    /// Whether `pane` can be split in `direction` without any pane ending up below its
+    /// minimum size, given the space its axis took up when the tree was last painted. A pane
+    /// that isn't laid out by an axis, such as a lone root pane, can always be split.
+    pub fn can_split(&self, pane: &View<Pane>, direction: SplitDirection, cx: &AppContext) -> bool {
+        let Member::Axis(root) = &self.root else {
+            return true;
+        };
+        let Some((axis, _)) = root.find_parent_axis(pane) else {
+            return true;
+        };
+        axis.painted_bounds().map_or(true, |bounds| {
+            axis.can_split(bounds.size.along(direction.axis()).into(), direction, cx)
+        })
+    }
+
+    /// Returns the bounds each pane was given when the tree was last painted. A lone root
+    /// pane isn't laid out by an axis, so it has no recorded bounds.
+    pub fn pane_bounds(&self) -> Vec<(View<Pane>, Bounds<Pixels>)> {
+        let mut pane_bounds = Vec::new();
+        if let Member::Axis(axis) = &self.root {
+            axis.collect_pane_bounds(&mut pane_bounds);
+        }
+        pane_bounds
+    }
+
+    /// Returns the pane under `coordinate`, based on the bounds recorded when the tree was
+    /// last painted. A point on the border between two panes resolves to the first of them.
     pub fn pane_at_pixel_position(&self, coordinate: Point<Pixels>) -> Option<&View<Pane>> {
         match &self.root {
             Member::Pane(pane) => Some(pane),
@@ -65,27 +211,448 @@ impl PaneGroup {
         }
     }
 
+    /// Returns the pane next to `from` in the given direction, based on the structure of
+    /// the tree rather than on-screen positions. When the neighbor is itself split, the
+    /// pane on the edge facing `from` is returned, picking the first pane of any axis
+    /// that runs across the direction of travel.
+    pub fn adjacent_pane(
+        &self,
+        from: &View<Pane>,
+        direction: SplitDirection,
+    ) -> Option<View<Pane>> {
+        self.root.adjacent_pane(from, direction).flatten()
+    }
+
+    /// The [`Self::adjacent_pane`] of `pane` in each direction, in [`SplitDirection::all`]
+    /// order.
+    pub fn neighbors(&self, pane: &View<Pane>) -> [Option<View<Pane>>; 4] {
+        SplitDirection::all().map(|direction| self.adjacent_pane(pane, direction))
+    }
+
+    /// The pane at the far end of the layout in `direction`. Axes across `direction` are
+    /// entered at their first member, so `Left` gives the top-left pane.
+    pub fn edge_pane(&self, direction: SplitDirection) -> View<Pane> {
+        self.root.entry_pane(direction.opposite())
+    }
+
+    /// Returns the orientation of the axis that directly contains `pane`, along with the
+    /// pane's index in that axis and the axis' member count. Returns `None` if `pane` isn't
+    /// in the tree or is its only pane.
+    pub fn find_axis_containing(&self, pane: &View<Pane>) -> Option<(Axis, usize, usize)> {
+        match &self.root {
+            Member::Pane(_) => None,
+            Member::Axis(axis) => {
+                let (axis, ix) = axis.find_parent_axis(pane)?;
+                Some((axis.axis, ix, axis.members.len()))
+            }
+        }
+    }
+
+    /// Moves `pane` one slot in `direction` within the axis that directly contains it, taking
+    /// its flex along so that it keeps its size. Returns `Ok(false)` if that axis doesn't run
+    /// in `direction` or the pane is already at its edge.
+    pub fn move_pane(&mut self, pane: &View<Pane>, direction: SplitDirection) -> Result<bool> {
+        if !self.root.contains(pane) {
+            return Err(anyhow!("Pane not found"));
+        }
+        let Member::Axis(axis) = &mut self.root else {
+            return Ok(false);
+        };
+        let Some(parent) = axis.axis_containing_mut(pane) else {
+            return Ok(false);
+        };
+        if parent.axis != direction.axis() {
+            return Ok(false);
+        }
+
+        let Some(ix) = parent
+            .members
+            .iter()
+            .position(|member| matches!(member, Member::Pane(member) if member == pane))
+        else {
+            return Ok(false);
+        };
+        let target_ix = if direction.increasing() {
+            ix + 1
+        } else if let Some(target_ix) = ix.checked_sub(1) {
+            target_ix
+        } else {
+            return Ok(false);
+        };
+        if target_ix >= parent.members.len() {
+            return Ok(false);
+        }
+
+        parent.members.swap(ix, target_ix);
+        parent.flexes.lock().swap(ix, target_ix);
+        parent.bounding_boxes.lock().swap(ix, target_ix);
+        Ok(true)
+    }
+
+    /// Takes `pane` out of its current position and splits `target` with it in `direction`,
+    /// as when a pane is dropped onto the edge of another one. Returns `Ok(false)` without
+    /// changing anything when `pane` is dropped onto itself.
+    pub fn relocate_pane(
+        &mut self,
+        pane: &View<Pane>,
+        target: &View<Pane>,
+        direction: SplitDirection,
+    ) -> Result<bool> {
+        if !self.root.contains(pane) || !self.root.contains(target) {
+            return Err(anyhow!("Pane not found"));
+        }
+        if pane == target {
+            return Ok(false);
+        }
+
+        self.remove(pane)?;
+        self.split(target, pane, direction)?;
+        Ok(true)
+    }
+
+    /// Flips the axis that directly contains `pane` between horizontal and vertical,
+    /// keeping the order and sizes of its members.
+    pub fn rotate_containing(&mut self, pane: &View<Pane>) -> Result<()> {
+        match &mut self.root {
+            Member::Pane(root) if root == pane => Ok(()),
+            Member::Pane(_) => Err(anyhow!("Pane not found")),
+            Member::Axis(axis) => {
+                let axis = axis
+                    .axis_containing_mut(pane)
+                    .ok_or_else(|| anyhow!("Pane not found"))?;
+                axis.rotate();
+                Ok(())
+            }
+        }
+    }
+
     /// Returns:
     /// - Ok(true) if it found and removed a pane
-    /// - Ok(false) if it found but did not remove the pane
+    /// - Ok(false) if it found but did not remove the pane, because it's the only one or
+    ///   it's locked
     /// - Err(_) if it did not find the pane
     pub fn remove(&mut self, pane: &View<Pane>) -> Result<bool> {
+        if self.locked.contains(pane) {
+            return Ok(false);
+        }
         match &mut self.root {
             Member::Pane(_) => Ok(false),
             Member::Axis(axis) => {
                 if let Some(last_pane) = axis.remove(pane)? {
                     self.root = last_pane;
                 }
+                self.minimized.remove(pane);
+                self.flatten();
                 Ok(true)
             }
         }
     }
 
-    pub fn swap(&mut self, from: &View<Pane>, to: &View<Pane>) {
+    /// Merges every axis into its parent axis when both have the same orientation, which
+    /// removing panes can leave behind. Each merged member keeps its share of the space.
+    pub fn flatten(&mut self) {
+        if let Member::Axis(axis) = &mut self.root {
+            axis.flatten();
+        }
+    }
+
+    /// Removes `pane` like [`Self::remove`], returning the surviving pane that was next to it:
+    /// its previous sibling if it had one, otherwise the following one.
+    pub fn remove_returning_neighbor(&mut self, pane: &View<Pane>) -> Result<Option<View<Pane>>> {
+        match &self.root {
+            Member::Pane(_) => Ok(None),
+            Member::Axis(axis) => {
+                let neighbor = axis
+                    .removal_neighbor(pane)
+                    .ok_or_else(|| anyhow!("Pane not found"))?;
+                Ok(self.remove(pane)?.then_some(neighbor))
+            }
+        }
+    }
+
+    /// Collapses the group down to just `keep`, returning the other panes so that the caller
+    /// can clean them up. Fails without removing anything if another pane is locked.
+    pub fn remove_all_except(&mut self, keep: &View<Pane>) -> Result<Vec<View<Pane>>> {
+        if !self.root.contains(keep) {
+            return Err(anyhow!("Pane not found"));
+        }
+        if self.locked.iter().any(|pane| pane != keep) {
+            return Err(anyhow!("Can't remove locked panes"));
+        }
+
+        let removed = self
+            .panes()
+            .into_iter()
+            .filter(|pane| *pane != keep)
+            .cloned()
+            .collect();
+        self.root = Member::Pane(keep.clone());
+        self.minimized.clear();
+        Ok(removed)
+    }
+
+    /// Exchanges the positions of two panes in the tree, leaving every axis'
+    /// flexes untouched so the visible sizes of each slot stay put.
+    pub fn swap(&mut self, from: &View<Pane>, to: &View<Pane>) -> Result<()> {
+        if !self.root.contains(from) || !self.root.contains(to) {
+            return Err(anyhow!("Pane not found"));
+        }
+
         match &mut self.root {
             Member::Pane(_) => {}
             Member::Axis(axis) => axis.swap(from, to),
         };
+        Ok(())
+    }
+
+    /// Puts `new` in the slot occupied by `old`, keeping the layout as is. If `old` was
+    /// minimized or locked, `new` is too.
+    pub fn replace_pane(&mut self, old: &View<Pane>, new: View<Pane>) -> Result<()> {
+        let member = self
+            .root
+            .pane_member_mut(old)
+            .ok_or_else(|| anyhow!("Pane not found"))?;
+        *member = Member::Pane(new.clone());
+        if let Some(flex) = self.minimized.remove(old) {
+            self.minimized.insert(new.clone(), flex);
+        }
+        if self.locked.remove(old) {
+            self.locked.insert(new);
+        }
+        Ok(())
+    }
+
+    /// The tree as JSON, for diagnosing layout bugs. Each axis lists its orientation, its
+    /// flexes, and the size of each member along it when it was last painted, or null for a
+    /// member that hasn't been. Panes are identified by their entity id.
+    pub fn debug_json(&self) -> serde_json::Value {
+        self.root.debug_json()
+    }
+
+    /// Whether both groups have the same axes, with the same number of members and flexes,
+    /// wherever the panes in them differ. Flexes only need to agree to within rounding.
+    pub fn same_shape(&self, other: &PaneGroup) -> bool {
+        self.root.same_shape(&other.root)
+    }
+
+    /// Builds a group with the same shape and flexes as this one, filled with new panes from
+    /// `make_pane` in [`Self::panes`] order. Minimized and locked panes stay so in the copy.
+    pub fn clone_layout_with(&self, make_pane: &mut impl FnMut() -> View<Pane>) -> PaneGroup {
+        let mut minimized = HashMap::default();
+        let mut locked = HashSet::default();
+        let root = self.root.clone_layout_with(&mut |pane| {
+            let new_pane = make_pane();
+            if let Some(flex) = self.minimized.get(pane) {
+                minimized.insert(new_pane.clone(), *flex);
+            }
+            if self.locked.contains(pane) {
+                locked.insert(new_pane.clone());
+            }
+            new_pane
+        });
+        PaneGroup {
+            root,
+            max_panes: self.max_panes,
+            minimized,
+            locked,
+        }
+    }
+
+    /// Replaces every pane in the tree with the one `f` returns for it, keeping the axes and
+    /// their flexes as they are. Used when the panes are recreated, e.g. on project reload.
+    pub fn map_panes(&mut self, mut f: impl FnMut(&View<Pane>) -> View<Pane>) {
+        let mut minimized = HashMap::default();
+        let mut locked = HashSet::default();
+        self.root.visit_mut(&mut |member| {
+            if let Member::Pane(pane) = member {
+                let new_pane = f(pane);
+                if let Some(flex) = self.minimized.remove(pane) {
+                    minimized.insert(new_pane.clone(), flex);
+                }
+                if self.locked.remove(pane) {
+                    locked.insert(new_pane.clone());
+                }
+                *pane = new_pane;
+            }
+        });
+        self.minimized = minimized;
+        self.locked = locked;
+    }
+
+    /// Grows the member containing `active_pane` towards `direction` by `amount`, a fraction
+    /// of the size of the nearest enclosing axis that has a neighbor in that direction. A
+    /// negative `amount` shrinks it instead. Like dragging a handle, no pane is shrunk below
+    /// the configured minimum size. Does nothing and returns false if there's no neighbor in
+    /// `direction`.
+    pub fn resize_active(
+        &mut self,
+        active_pane: &View<Pane>,
+        direction: SplitDirection,
+        amount: f32,
+        cx: &AppContext,
+    ) -> bool {
+        match &self.root {
+            Member::Axis(axis) => axis.resize_containing(active_pane, direction, amount, cx),
+            Member::Pane(_) => false,
+        }
+    }
+
+    /// Moves `pane` one slot towards `direction`, like [`Self::move_pane`], then grows it by
+    /// `grow` like [`Self::resize_active`]. It grows towards `direction` if it still has a
+    /// neighbor there, and back the way it came otherwise, so a pane that's already at the
+    /// edge only grows. Returns whether the pane moved or grew.
+    pub fn promote(
+        &mut self,
+        pane: &View<Pane>,
+        direction: SplitDirection,
+        grow: f32,
+        cx: &AppContext,
+    ) -> Result<bool> {
+        let moved = self.move_pane(pane, direction)?;
+        let grew = match &self.root {
+            Member::Axis(axis) => {
+                axis.resize_containing(pane, direction, grow, cx)
+                    || axis.resize_containing(pane, direction.opposite(), grow, cx)
+            }
+            Member::Pane(_) => false,
+        };
+        Ok(moved || grew)
+    }
+
+    /// Shrinks `pane` to a thin strip in its axis, handing its space to its siblings, until
+    /// [`Self::restore`] is called. Minimizing an already minimized pane does nothing.
+    pub fn minimize(&mut self, pane: &View<Pane>) -> Result<()> {
+        if self.minimized.contains_key(pane) {
+            return Ok(());
+        }
+        let Member::Axis(root) = &self.root else {
+            return Err(anyhow!("Can't minimize a pane without siblings"));
+        };
+        let (axis, ix) = root
+            .find_parent_axis(pane)
+            .ok_or_else(|| anyhow!("Pane not found"))?;
+
+        let mut flexes = axis.flexes.lock();
+        self.minimized.insert(pane.clone(), flexes[ix]);
+        element::set_flex(&mut flexes, ix, element::MIN_FLEX);
+        Ok(())
+    }
+
+    /// Gives a pane minimized by [`Self::minimize`] back the flex it had before, taking the
+    /// space from its siblings in proportion to their current sizes.
+    pub fn restore(&mut self, pane: &View<Pane>) -> Result<()> {
+        let flex = self
+            .minimized
+            .remove(pane)
+            .ok_or_else(|| anyhow!("Pane isn't minimized"))?;
+        if let Member::Axis(root) = &self.root {
+            if let Some((axis, ix)) = root.find_parent_axis(pane) {
+                element::set_flex(&mut axis.flexes.lock(), ix, flex);
+            }
+        }
+        Ok(())
+    }
+
+    /// The focus handle of the divider after `pane`, or before it when `pane` is the last
+    /// member of its axis. None until the group has been rendered, or if `pane` has no
+    /// siblings.
+    pub fn divider_focus_handle(&self, pane: &View<Pane>) -> Option<FocusHandle> {
+        let Member::Axis(root) = &self.root else {
+            return None;
+        };
+        let (axis, ix) = root.find_parent_axis(pane)?;
+        let divider_ix = ix.min(axis.members.len().checked_sub(2)?);
+        axis.divider_focus.lock().get(divider_ix).cloned()
+    }
+
+    pub fn is_minimized(&self, pane: &View<Pane>) -> bool {
+        self.minimized.contains_key(pane)
+    }
+
+    /// Locks or unlocks `pane`. A locked pane can't be removed from the group, so closing
+    /// its last item leaves it empty instead.
+    pub fn set_locked(&mut self, pane: &View<Pane>, locked: bool) -> Result<()> {
+        if !self.root.contains(pane) {
+            return Err(anyhow!("Pane not found"));
+        }
+        if locked {
+            self.locked.insert(pane.clone());
+        } else {
+            self.locked.remove(pane);
+        }
+        Ok(())
+    }
+
+    pub fn is_locked(&self, pane: &View<Pane>) -> bool {
+        self.locked.contains(pane)
+    }
+
+    /// Like [`Self::equalize`], but only for the axis directly containing `pane`.
+    pub fn rebalance_containing(&mut self, pane: &View<Pane>) -> Result<()> {
+        let not_found = || anyhow!("Pane not found");
+        let Member::Axis(root) = &self.root else {
+            return if self.root.contains(pane) {
+                Ok(())
+            } else {
+                Err(not_found())
+            };
+        };
+        let (axis, _) = root.find_parent_axis(pane).ok_or_else(not_found)?;
+        *axis.flexes.lock() = vec![1.; axis.members.len()];
+        for member in &axis.members {
+            if let Member::Pane(pane) = member {
+                self.minimized.remove(pane);
+            }
+        }
+        Ok(())
+    }
+
+    /// Discards the current nesting and lays every pane out side by side along `axis`, in
+    /// [`Self::panes`] order and with equal flexes.
+    pub fn tile_evenly(&mut self, axis: Axis) {
+        let members = self
+            .panes()
+            .into_iter()
+            .cloned()
+            .map(Member::Pane)
+            .collect();
+        self.root = Member::from_members(axis, members);
+        self.minimized.clear();
+    }
+
+    /// Applies `action`, picked from the context menu of handle `ix`, to the axis laid out
+    /// with `flexes`.
+    pub(crate) fn apply_handle_action(
+        &mut self,
+        flexes: &Arc<Mutex<Vec<f32>>>,
+        ix: usize,
+        action: HandleAction,
+    ) -> Result<()> {
+        let axis = self
+            .root
+            .axis_with_flexes_mut(flexes)
+            .ok_or_else(|| anyhow!("Axis not found"))?;
+        match action {
+            HandleAction::Equalize => *axis.flexes.lock() = vec![1.; axis.members.len()],
+            HandleAction::Reset => {
+                element::equalize_adjacent(axis.flexes.lock().as_mut_slice(), ix)
+            }
+            HandleAction::RotateAxis => axis.rotate(),
+        }
+        if action == HandleAction::RotateAxis {
+            // The axis may now match its parent or its children
+            self.flatten();
+        }
+        Ok(())
+    }
+
+    /// Resets every axis in the tree so that its members share space equally.
+    pub fn equalize(&mut self) {
+        self.visit_mut(|member| {
+            if let Member::Axis(axis) = member {
+                *axis.flexes.lock() = vec![1.; axis.members.len()];
+            }
+        });
     }
 
     pub(crate) fn render(
@@ -98,6 +665,7 @@ impl PaneGroup {
         app_state: &Arc<AppState>,
         cx: &mut ViewContext<Workspace>,
     ) -> impl IntoElement {
+        self.update_hovered_handle(cx.mouse_position(), cx);
         self.root.render(
             project,
             0,
@@ -105,30 +673,328 @@ impl PaneGroup {
             active_call,
             active_pane,
             zoomed,
+            &self.minimized,
             app_state,
             cx,
         )
     }
 
+    /// The handle under `position` as of the last layout, as the axis it belongs to and its
+    /// index. Where the handles of nested axes overlap, such as where a split inside a column
+    /// meets the edge of the column, the innermost one wins.
+    fn handle_at(&self, position: Point<Pixels>, cx: &AppContext) -> Option<(&PaneAxis, usize)> {
+        let settings = WorkspaceSettings::get_global(cx);
+        let mut handles = Vec::new();
+        self.root.collect_handles(
+            0,
+            px(settings.pane_split_handle_size),
+            px(settings.pane_gap),
+            &mut handles,
+        );
+        let handle_bounds = handles
+            .iter()
+            .map(|(depth, bounds, _, _)| (*depth, *bounds))
+            .collect::<Vec<_>>();
+        element::hovered_handle(&handle_bounds, position).map(|handle_ix| {
+            let (_, _, axis, ix) = handles[handle_ix];
+            (axis, ix)
+        })
+    }
+
+    /// Whether handle `ix` of the axis laid out with `flexes` is the one that should respond
+    /// to the mouse at `position`.
+    pub(crate) fn claims_handle(
+        &self,
+        flexes: &Arc<Mutex<Vec<f32>>>,
+        ix: usize,
+        position: Point<Pixels>,
+        cx: &AppContext,
+    ) -> bool {
+        self.handle_at(position, cx)
+            .map_or(false, |(axis, handle_ix)| {
+                Arc::ptr_eq(&axis.flexes, flexes) && handle_ix == ix
+            })
+    }
+
+    /// Records on each axis whether it has the handle under `position`, so that only one
+    /// handle in the group shows as hovered.
+    fn update_hovered_handle(&self, position: Point<Pixels>, cx: &AppContext) {
+        let hovered = self
+            .handle_at(position, cx)
+            .map(|(axis, ix)| (axis.flexes.clone(), ix));
+        self.root.visit(&mut |member| {
+            if let Member::Axis(axis) = member {
+                *axis.hovered_handle.lock() = hovered
+                    .as_ref()
+                    .filter(|(flexes, _)| Arc::ptr_eq(flexes, &axis.flexes))
+                    .map(|(_, ix)| *ix);
+            }
+        });
+    }
+
+    /// All panes in the group, depth first: each axis lists its members left to right or top
+    /// to bottom, with a nested axis' panes in place of the axis. So a row of columns yields
+    /// the whole first column before the second, while a column of rows reads like text.
     pub(crate) fn panes(&self) -> Vec<&View<Pane>> {
         let mut panes = Vec::new();
         self.root.collect_panes(&mut panes);
         panes
     }
 
+    /// The orientation and a snapshot of the flexes of every axis in the group, each listed
+    /// before the axes nested in it, in the same order as [`Self::panes`].
+    pub fn axes(&self) -> Vec<(Axis, Vec<f32>)> {
+        let mut axes = Vec::new();
+        self.root.visit(&mut |member| {
+            if let Member::Axis(axis) = member {
+                axes.push((axis.axis, axis.flexes()));
+            }
+        });
+        axes
+    }
+
+    /// Sets the flexes of each axis found at a path in `flexes_by_path`, as with
+    /// [`PaneAxis::set_flexes`]. Axes that aren't listed keep their flexes. Nothing changes
+    /// if any path doesn't lead to an axis or any list of flexes has the wrong length.
+    pub fn apply_flexes(&mut self, flexes_by_path: &HashMap<Vec<usize>, Vec<f32>>) -> Result<()> {
+        let mut axes = Vec::with_capacity(flexes_by_path.len());
+        for (path, flexes) in flexes_by_path {
+            match self.member_at_path(path) {
+                Some(Member::Axis(axis)) if axis.members.len() == flexes.len() => {
+                    axes.push((axis, flexes))
+                }
+                Some(Member::Axis(axis)) => {
+                    return Err(anyhow!(
+                        "Expected {} flexes at {path:?} but got {}",
+                        axis.members.len(),
+                        flexes.len()
+                    ))
+                }
+                Some(Member::Pane(_)) => {
+                    return Err(anyhow!("Path {path:?} leads to a pane, not an axis"))
+                }
+                None => return Err(anyhow!("Path {path:?} doesn't lead to a member")),
+            }
+        }
+        for (axis, flexes) in axes {
+            axis.set_flexes(flexes.clone())?;
+        }
+        Ok(())
+    }
+
+    /// The member indices leading from the root to `pane`, which are empty if `pane` is the
+    /// root itself. See [`Self::pane_at_path`] for the reverse.
+    pub fn path_to_pane(&self, pane: &View<Pane>) -> Option<Vec<usize>> {
+        let mut path = Vec::new();
+        self.root.path_to_pane(pane, &mut path).then_some(path)
+    }
+
+    pub fn pane_at_path(&self, path: &[usize]) -> Option<&View<Pane>> {
+        match self.member_at_path(path)? {
+            Member::Pane(pane) => Some(pane),
+            Member::Axis(_) => None,
+        }
+    }
+
+    /// Like [`Self::split`], but splits the pane at `path`, as resolved by
+    /// [`Self::pane_at_path`].
+    pub fn split_at_path(
+        &mut self,
+        path: &[usize],
+        new_pane: &View<Pane>,
+        direction: SplitDirection,
+    ) -> Result<()> {
+        let old_pane = match self.member_at_path(path) {
+            Some(Member::Pane(pane)) => pane.clone(),
+            Some(Member::Axis(_)) => {
+                return Err(anyhow!("Path {path:?} leads to an axis, not a pane"))
+            }
+            None => return Err(anyhow!("Path {path:?} doesn't lead to a member")),
+        };
+        self.split(&old_pane, new_pane, direction)
+    }
+
+    /// Returns the orientation of the deepest axis containing both `a` and `b`, or `None` if
+    /// either isn't in the tree.
+    pub fn common_ancestor(&self, a: &View<Pane>, b: &View<Pane>) -> Option<Axis> {
+        let path_a = self.path_to_pane(a)?;
+        let path_b = self.path_to_pane(b)?;
+        let shared = path_a
+            .iter()
+            .zip(&path_b)
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        // The shared path only leads to a pane when `a` and `b` are the same one
+        match self.member_at_path(&path_a[..shared])? {
+            Member::Axis(axis) => Some(axis.axis),
+            Member::Pane(_) => match self.member_at_path(&path_a[..shared.checked_sub(1)?])? {
+                Member::Axis(axis) => Some(axis.axis),
+                Member::Pane(_) => None,
+            },
+        }
+    }
+
+    /// How the panes of `other`, a later version of this layout, differ from this one's.
+    pub fn diff(&self, other: &PaneGroup) -> LayoutDiff {
+        let mut diff = LayoutDiff::default();
+        let in_both = |pane: &View<Pane>| self.root.contains(pane) && other.root.contains(pane);
+        for pane in self.panes() {
+            if !other.root.contains(pane) {
+                diff.removed.push(pane.clone());
+            } else if self.parent_panes(pane, in_both) != other.parent_panes(pane, in_both) {
+                diff.reparented.push(pane.clone());
+            }
+        }
+        diff.added = other
+            .panes()
+            .into_iter()
+            .filter(|pane| !self.root.contains(pane))
+            .cloned()
+            .collect();
+        diff
+    }
+
+    /// The panes matching `filter` under the axis that directly contains `pane`, or `None` if
+    /// `pane` is the root.
+    fn parent_panes(
+        &self,
+        pane: &View<Pane>,
+        filter: impl Fn(&View<Pane>) -> bool,
+    ) -> Option<HashSet<View<Pane>>> {
+        let path = self.path_to_pane(pane)?;
+        let parent = self.member_at_path(&path[..path.len().checked_sub(1)?])?;
+        let mut panes = Vec::new();
+        parent.collect_panes(&mut panes);
+        Some(
+            panes
+                .into_iter()
+                .filter(|pane| filter(pane))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    fn member_at_path(&self, path: &[usize]) -> Option<&Member> {
+        let mut member = &self.root;
+        for ix in path {
+            match member {
+                Member::Axis(axis) => member = axis.members.get(*ix)?,
+                Member::Pane(_) => return None,
+            }
+        }
+        Some(member)
+    }
+
+    /// Calls `f` with every member of the tree, see [`Member::visit`].
+    pub(crate) fn visit<'a>(&'a self, mut f: impl FnMut(&'a Member)) {
+        self.root.visit(&mut f);
+    }
+
+    /// Calls `f` with every member of the tree, see [`Member::visit_mut`].
+    pub(crate) fn visit_mut(&mut self, mut f: impl FnMut(&mut Member)) {
+        self.root.visit_mut(&mut f);
+    }
+
+    /// Converts the group to the form it's persisted in, with `describe` serializing each pane.
+    /// Whether a pane is locked is taken from the group rather than from `describe`.
+    pub fn to_serialized(
+        &self,
+        describe: impl Fn(&View<Pane>) -> SerializedPane,
+    ) -> SerializedPaneGroup {
+        self.root
+            .to_serialized(&|pane| describe(pane).with_locked(self.is_locked(pane)))
+    }
+
+    /// Builds a group from the form it's persisted in, with `open` creating a pane for each
+    /// serialized one. Axes without any panes are dropped, and an axis left with one member is
+    /// replaced by that member. Fails if there are no panes at all.
+    pub fn from_serialized(
+        serialized: &SerializedPaneGroup,
+        mut open: impl FnMut(&SerializedPane) -> View<Pane>,
+    ) -> Result<Self> {
+        let mut locked = HashSet::default();
+        let root = Member::from_serialized(serialized, &mut |serialized_pane| {
+            let pane = open(serialized_pane);
+            if serialized_pane.locked {
+                locked.insert(pane.clone());
+            }
+            pane
+        })
+        .ok_or_else(|| anyhow!("Serialized pane group has no panes"))?;
+        let mut group = Self::with_root(root);
+        group.locked = locked;
+        Ok(group)
+    }
+
     pub(crate) fn first_pane(&self) -> View<Pane> {
         self.root.first_pane()
     }
+
+    /// The number of panes in the group.
+    pub fn len(&self) -> usize {
+        self.root.len()
+    }
+
+    /// Whether the group has no panes. The root is always a pane or a non-empty axis, so this
+    /// only exists to pair with `len`.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// How deeply axes are nested within the group, where a lone pane has a depth of 0.
+    pub fn depth(&self) -> usize {
+        self.root.depth()
+    }
+
+    /// Describes the shape of the tree, like `h[v[a,b],c]`, where `h` and `v` are horizontal
+    /// and vertical axes and the panes are named `a`, `b`, ... in the order they appear.
+    /// Sizes aren't included.
+    pub fn to_layout_string(&self) -> String {
+        let panes = self.panes();
+        let mut layout = String::new();
+        self.root.write_layout(&panes, &mut layout);
+        layout
+    }
+
+    /// Builds a group from a layout in the format of [`Self::to_layout_string`], giving every
+    /// axis' members equal sizes. Pane names can be any run of letters, digits and
+    /// underscores, and are turned into panes by `resolve`.
+    pub fn from_layout_string(
+        layout: &str,
+        resolve: impl FnMut(&str) -> Option<View<Pane>>,
+    ) -> Result<Self> {
+        let mut parser = LayoutParser {
+            layout,
+            position: 0,
+            resolve,
+            seen: HashSet::default(),
+        };
+        let root = parser.member()?;
+        parser.skip_whitespace();
+        if let Some(c) = parser.peek() {
+            return Err(anyhow!(
+                "Unexpected '{c}' at {} in layout {layout:?}",
+                parser.position
+            ));
+        }
+        Ok(Self::with_root(root))
+    }
 }
 
 #[derive(Clone)]
-pub(crate) enum Member {
+pub enum Member {
     Axis(PaneAxis),
     Pane(View<Pane>),
 }
 
 impl Member {
-    fn new_axis(old_pane: View<Pane>, new_pane: View<Pane>, direction: SplitDirection) -> Self {
+    fn new_axis(
+        old_pane: View<Pane>,
+        new_pane: View<Pane>,
+        direction: SplitDirection,
+        ratio: Option<f32>,
+    ) -> Self {
         use Axis::*;
         use SplitDirection::*;
 
@@ -137,18 +1003,128 @@ impl Member {
             Left | Right => Horizontal,
         };
 
-        let members = match direction {
-            Up | Left => vec![Member::Pane(new_pane), Member::Pane(old_pane)],
-            Down | Right => vec![Member::Pane(old_pane), Member::Pane(new_pane)],
+        let (old_flex, new_flex) = ratio.map_or((1., 1.), |ratio| (2. * (1. - ratio), 2. * ratio));
+        let (members, flexes, new_ix) = match direction {
+            Up | Left => (
+                vec![Member::Pane(new_pane), Member::Pane(old_pane)],
+                vec![new_flex, old_flex],
+                0,
+            ),
+            Down | Right => (
+                vec![Member::Pane(old_pane), Member::Pane(new_pane)],
+                vec![old_flex, new_flex],
+                1,
+            ),
         };
 
-        Member::Axis(PaneAxis::new(axis, members))
+        let axis = PaneAxis::load(axis, members, Some(flexes));
+        axis.animate_insertion(new_ix, &[1.]);
+        Member::Axis(axis)
     }
 
-    fn contains(&self, needle: &View<Pane>) -> bool {
-        match self {
-            Member::Axis(axis) => axis.members.iter().any(|member| member.contains(needle)),
-            Member::Pane(pane) => pane == needle,
+    /// Wraps `members` in an axis with uniform flexes, or returns a lone member as-is.
+    fn from_members(axis: Axis, mut members: Vec<Member>) -> Self {
+        if members.len() == 1 {
+            members.pop().unwrap()
+        } else {
+            Member::Axis(PaneAxis::new(axis, members))
+        }
+    }
+
+    fn axis_with_flexes_mut(&mut self, flexes: &Arc<Mutex<Vec<f32>>>) -> Option<&mut PaneAxis> {
+        match self {
+            Member::Axis(axis) if Arc::ptr_eq(&axis.flexes, flexes) => Some(axis),
+            Member::Axis(axis) => axis
+                .members
+                .iter_mut()
+                .find_map(|member| member.axis_with_flexes_mut(flexes)),
+            Member::Pane(_) => None,
+        }
+    }
+
+    fn pane_member_mut(&mut self, needle: &View<Pane>) -> Option<&mut Member> {
+        if matches!(self, Member::Pane(pane) if pane == needle) {
+            return Some(self);
+        }
+        match self {
+            Member::Axis(axis) => axis
+                .members
+                .iter_mut()
+                .find_map(|member| member.pane_member_mut(needle)),
+            Member::Pane(_) => None,
+        }
+    }
+
+    fn contains(&self, needle: &View<Pane>) -> bool {
+        match self {
+            Member::Axis(axis) => axis.members.iter().any(|member| member.contains(needle)),
+            Member::Pane(pane) => pane == needle,
+        }
+    }
+
+    /// How short this member can be made along `axis`: the largest of its panes' minimums
+    /// across the axis, and their sum when its panes are lined up along it.
+    fn min_size(&self, axis: Axis, cx: &AppContext) -> Pixels {
+        match self {
+            Member::Axis(member_axis) => {
+                let min_sizes = member_axis
+                    .members
+                    .iter()
+                    .map(|member| member.min_size(axis, cx));
+                if member_axis.axis == axis {
+                    min_sizes.fold(px(0.), |total, min_size| total + min_size)
+                } else {
+                    min_sizes.fold(px(0.), Pixels::max)
+                }
+            }
+            Member::Pane(pane) => Pixels::max(
+                min_pane_size(axis, cx),
+                pane.read(cx).min_size().along(axis),
+            ),
+        }
+    }
+
+    fn to_serialized(
+        &self,
+        describe: &dyn Fn(&View<Pane>) -> SerializedPane,
+    ) -> SerializedPaneGroup {
+        match self {
+            Member::Axis(axis) => SerializedPaneGroup::Group {
+                axis: SerializedAxis(axis.axis),
+                flexes: Some(axis.flexes()),
+                children: axis
+                    .members
+                    .iter()
+                    .map(|member| member.to_serialized(describe))
+                    .collect(),
+            },
+            Member::Pane(pane) => SerializedPaneGroup::Pane(describe(pane)),
+        }
+    }
+
+    fn from_serialized(
+        serialized: &SerializedPaneGroup,
+        open: &mut dyn FnMut(&SerializedPane) -> View<Pane>,
+    ) -> Option<Self> {
+        match serialized {
+            SerializedPaneGroup::Group {
+                axis,
+                flexes,
+                children,
+            } => {
+                let mut members = children
+                    .iter()
+                    .filter_map(|child| Self::from_serialized(child, open))
+                    .collect::<Vec<_>>();
+                if members.len() <= 1 {
+                    return members.pop();
+                }
+                let flexes = flexes
+                    .clone()
+                    .filter(|flexes| flexes.len() == members.len());
+                Some(Member::Axis(PaneAxis::load(axis.0, members, flexes)))
+            }
+            SerializedPaneGroup::Pane(pane) => Some(Member::Pane(open(pane))),
         }
     }
 
@@ -159,7 +1135,49 @@ impl Member {
         }
     }
 
-    pub fn render(
+    /// Returns `None` if `from` isn't in this member, `Some(None)` if it is but there's no
+    /// neighbor in `direction` within this member.
+    fn adjacent_pane(
+        &self,
+        from: &View<Pane>,
+        direction: SplitDirection,
+    ) -> Option<Option<View<Pane>>> {
+        match self {
+            Member::Pane(pane) => (pane == from).then_some(None),
+            Member::Axis(axis) => {
+                let (ix, adjacent) =
+                    axis.members.iter().enumerate().find_map(|(ix, member)| {
+                        Some((ix, member.adjacent_pane(from, direction)?))
+                    })?;
+                if adjacent.is_some() || axis.axis != direction.axis() {
+                    return Some(adjacent);
+                }
+
+                let neighbor_ix = if direction.increasing() {
+                    Some(ix + 1).filter(|ix| *ix < axis.members.len())
+                } else {
+                    ix.checked_sub(1)
+                };
+                Some(neighbor_ix.map(|ix| axis.members[ix].entry_pane(direction)))
+            }
+        }
+    }
+
+    /// The first pane reached when moving into this member in the given direction.
+    fn entry_pane(&self, direction: SplitDirection) -> View<Pane> {
+        match self {
+            Member::Axis(axis) => {
+                if axis.axis == direction.axis() && !direction.increasing() {
+                    axis.members[axis.members.len() - 1].entry_pane(direction)
+                } else {
+                    axis.members[0].entry_pane(direction)
+                }
+            }
+            Member::Pane(pane) => pane.clone(),
+        }
+    }
+
+    pub(crate) fn render(
         &self,
         project: &Model<Project>,
         basis: usize,
@@ -167,6 +1185,7 @@ impl Member {
         active_call: Option<&Model<ActiveCall>>,
         active_pane: &View<Pane>,
         zoomed: Option<&AnyWeakView>,
+        minimized: &HashMap<View<Pane>, f32>,
         app_state: &Arc<AppState>,
         cx: &mut ViewContext<Workspace>,
     ) -> impl IntoElement {
@@ -235,12 +1254,10 @@ impl Member {
                         )
                     })
                     .when_some(leader_status_box, |this, status_box| {
+                        let corner = cx.theme().colors().leader_status_corner;
                         this.child(
-                            div()
-                                .absolute()
-                                .w_96()
-                                .bottom_3()
-                                .right_3()
+                            corner
+                                .position(div().absolute().w_96())
                                 .elevation_2(cx)
                                 .p_1()
                                 .z_index(1)
@@ -274,6 +1291,7 @@ impl Member {
                     active_call,
                     active_pane,
                     zoomed,
+                    minimized,
                     app_state,
                     cx,
                 )
@@ -281,50 +1299,278 @@ impl Member {
         }
     }
 
-    fn collect_panes<'a>(&'a self, panes: &mut Vec<&'a View<Pane>>) {
+    fn len(&self) -> usize {
+        let mut len = 0;
+        self.visit(&mut |member| {
+            if let Member::Pane(_) = member {
+                len += 1;
+            }
+        });
+        len
+    }
+
+    fn depth(&self) -> usize {
+        match self {
+            Member::Axis(axis) => 1 + axis.members.iter().map(Member::depth).max().unwrap_or(0),
+            Member::Pane(_) => 0,
+        }
+    }
+
+    fn write_layout(&self, panes: &[&View<Pane>], layout: &mut String) {
         match self {
+            Member::Pane(pane) => {
+                let ix = panes.iter().position(|p| *p == pane).unwrap_or_default();
+                layout.push_str(&layout_pane_name(ix));
+            }
             Member::Axis(axis) => {
-                for member in &axis.members {
-                    member.collect_panes(panes);
+                layout.push_str(match axis.axis {
+                    Axis::Horizontal => "h[",
+                    Axis::Vertical => "v[",
+                });
+                for (ix, member) in axis.members.iter().enumerate() {
+                    if ix > 0 {
+                        layout.push(',');
+                    }
+                    member.write_layout(panes, layout);
+                }
+                layout.push(']');
+            }
+        }
+    }
+
+    fn path_to_pane(&self, pane: &View<Pane>, path: &mut Vec<usize>) -> bool {
+        match self {
+            Member::Pane(member) => member == pane,
+            Member::Axis(axis) => axis.members.iter().enumerate().any(|(ix, member)| {
+                path.push(ix);
+                member.path_to_pane(pane, path) || {
+                    path.pop();
+                    false
+                }
+            }),
+        }
+    }
+
+    /// See [`PaneGroup::debug_json`].
+    fn debug_json(&self) -> serde_json::Value {
+        match self {
+            Member::Pane(pane) => serde_json::json!({ "pane": pane.entity_id().as_u64() }),
+            Member::Axis(axis) => serde_json::json!({
+                "axis": match axis.axis {
+                    Axis::Horizontal => "horizontal",
+                    Axis::Vertical => "vertical",
+                },
+                "flexes": axis.flexes(),
+                "sizes": axis
+                    .bounding_boxes
+                    .lock()
+                    .iter()
+                    .map(|bounds| bounds.map(|bounds| bounds.size.along(axis.axis).0))
+                    .collect::<Vec<_>>(),
+                "children": axis.members.iter().map(Member::debug_json).collect::<Vec<_>>(),
+            }),
+        }
+    }
+
+    /// See [`PaneGroup::same_shape`].
+    fn same_shape(&self, other: &Member) -> bool {
+        match (self, other) {
+            (Member::Pane(_), Member::Pane(_)) => true,
+            (Member::Axis(axis), Member::Axis(other)) => {
+                axis.axis == other.axis
+                    && axis.members.len() == other.members.len()
+                    && axis
+                        .flexes()
+                        .iter()
+                        .zip(other.flexes())
+                        .all(|(flex, other_flex)| (flex - other_flex).abs() < 0.0001)
+                    && axis
+                        .members
+                        .iter()
+                        .zip(&other.members)
+                        .all(|(member, other_member)| member.same_shape(other_member))
+            }
+            _ => false,
+        }
+    }
+
+    /// A copy of this subtree with its own flexes, and `make_pane`'s result in place of each
+    /// pane. Cloning a member instead shares the flexes of its axes with the original.
+    fn clone_layout_with(&self, make_pane: &mut impl FnMut(&View<Pane>) -> View<Pane>) -> Self {
+        match self {
+            Member::Pane(pane) => Member::Pane(make_pane(pane)),
+            Member::Axis(axis) => Member::Axis(PaneAxis::load(
+                axis.axis,
+                axis.members
+                    .iter()
+                    .map(|member| member.clone_layout_with(make_pane))
+                    .collect(),
+                Some(axis.flexes()),
+            )),
+        }
+    }
+
+    fn collect_panes<'a>(&'a self, panes: &mut Vec<&'a View<Pane>>) {
+        self.visit(&mut |member| {
+            if let Member::Pane(pane) = member {
+                panes.push(pane);
+            }
+        });
+    }
+
+    /// Collects the bounds of each handle in the subtree as of the last layout, along with its
+    /// nesting depth, axis, and index within the axis.
+    fn collect_handles<'a>(
+        &'a self,
+        depth: usize,
+        handle_size: Pixels,
+        gap: Pixels,
+        handles: &mut Vec<(usize, Bounds<Pixels>, &'a PaneAxis, usize)>,
+    ) {
+        if let Member::Axis(axis) = self {
+            let handle_count = axis.members.len().saturating_sub(1);
+            for (ix, bounds) in axis.bounding_boxes.lock().iter().enumerate() {
+                if let Some(bounds) = bounds.filter(|_| ix < handle_count) {
+                    let bounds = element::handle_bounds(axis.axis, bounds, handle_size, gap);
+                    handles.push((depth, bounds, axis, ix));
                 }
             }
-            Member::Pane(pane) => panes.push(pane),
+            for member in &axis.members {
+                member.collect_handles(depth + 1, handle_size, gap, handles);
+            }
+        }
+    }
+
+    /// Walks the tree depth first, calling `f` with each axis before its members, in order.
+    pub(crate) fn visit<'a>(&'a self, f: &mut impl FnMut(&'a Member)) {
+        f(self);
+        if let Member::Axis(axis) = self {
+            for member in &axis.members {
+                member.visit(f);
+            }
+        }
+    }
+
+    /// Like [`Self::visit`], but `f` may modify each member before its own members are
+    /// visited.
+    pub(crate) fn visit_mut(&mut self, f: &mut impl FnMut(&mut Member)) {
+        f(self);
+        if let Member::Axis(axis) = self {
+            for member in &mut axis.members {
+                member.visit_mut(f);
+            }
         }
     }
 }
 
 #[derive(Clone)]
-pub(crate) struct PaneAxis {
-    pub axis: Axis,
-    pub members: Vec<Member>,
-    pub flexes: Arc<Mutex<Vec<f32>>>,
-    pub bounding_boxes: Arc<Mutex<Vec<Option<Bounds<Pixels>>>>>,
+pub struct PaneAxis {
+    pub(crate) axis: Axis,
+    pub(crate) members: Vec<Member>,
+    pub(crate) flexes: Arc<Mutex<Vec<f32>>>,
+    pub(crate) bounding_boxes: Arc<Mutex<Vec<Option<Bounds<Pixels>>>>>,
+    /// How far the members are scrolled along the axis when they overflow it.
+    pub(crate) scroll_offset: Arc<Mutex<Pixels>>,
+    /// The focus handle of each divider, created when the axis is first rendered.
+    pub(crate) divider_focus: Arc<Mutex<Vec<FocusHandle>>>,
+    /// The transition the members are part way through after a split or removal.
+    pub(crate) animation: Arc<Mutex<Option<FlexAnimation>>>,
+    /// The index of the handle under the mouse, if no other axis in the group claims it.
+    pub(crate) hovered_handle: Arc<Mutex<Option<usize>>>,
+}
+
+/// A transition of an axis' laid out flexes from `from` to its stored flexes, which is
+/// dropped once it finishes or the members change again.
+#[derive(Clone, Debug)]
+pub(crate) struct FlexAnimation {
+    pub from: Vec<f32>,
+    pub started_at: Instant,
 }
 
 impl PaneAxis {
     pub fn new(axis: Axis, members: Vec<Member>) -> Self {
-        let flexes = Arc::new(Mutex::new(vec![1.; members.len()]));
-        let bounding_boxes = Arc::new(Mutex::new(vec![None; members.len()]));
-        Self {
+        Self::load(axis, members, None)
+    }
+
+    pub fn load(axis: Axis, members: Vec<Member>, flexes: Option<Vec<f32>>) -> Self {
+        let mut this = Self {
             axis,
-            members,
-            flexes,
-            bounding_boxes,
+            members: Vec::new(),
+            flexes: Default::default(),
+            bounding_boxes: Default::default(),
+            scroll_offset: Default::default(),
+            divider_focus: Default::default(),
+            animation: Default::default(),
+            hovered_handle: Default::default(),
+        };
+        for member in members {
+            this.insert_at(this.members.len(), member, 1.).unwrap();
+        }
+        // Stored flexes are taken as-is, rather than inserted one at a time, as normalizing
+        // after each insertion would change their ratios.
+        if let Some(flexes) = flexes {
+            debug_assert!(this.members.len() == flexes.len());
+            *this.flexes.lock() = flexes;
         }
+        this
     }
 
-    pub fn load(axis: Axis, members: Vec<Member>, flexes: Option<Vec<f32>>) -> Self {
-        let flexes = flexes.unwrap_or_else(|| vec![1.; members.len()]);
-        debug_assert!(members.len() == flexes.len());
+    /// Animates the member just inserted at `ix` growing in from nothing, while the others
+    /// go from `old_flexes` to their current flexes.
+    fn animate_insertion(&self, ix: usize, old_flexes: &[f32]) {
+        let mut from = old_flexes.to_vec();
+        from.insert(ix, 0.);
+        self.animate_from(from);
+    }
 
-        let flexes = Arc::new(Mutex::new(flexes));
-        let bounding_boxes = Arc::new(Mutex::new(vec![None; members.len()]));
-        Self {
-            axis,
-            members,
-            flexes,
-            bounding_boxes,
+    /// Starts laying the members out at `from`, moving towards their flexes over the next few
+    /// frames. `from` is normalized, so it only needs the right proportions.
+    fn animate_from(&self, mut from: Vec<f32>) {
+        if from.len() != self.members.len() {
+            return;
+        }
+        element::normalize_flexes(&mut from);
+        *self.animation.lock() = Some(FlexAnimation {
+            from,
+            started_at: Instant::now(),
+        });
+    }
+
+    /// A snapshot of the relative sizes of the members.
+    pub fn flexes(&self) -> Vec<f32> {
+        self.flexes.lock().clone()
+    }
+
+    /// Replaces the relative sizes of the members, which must have one value per member.
+    /// The values are normalized the same way as after a drag.
+    pub fn set_flexes(&self, mut flexes: Vec<f32>) -> Result<()> {
+        if flexes.len() != self.members.len() {
+            return Err(anyhow!(
+                "Expected {} flexes but got {}",
+                self.members.len(),
+                flexes.len()
+            ));
+        }
+        element::normalize_flexes(&mut flexes);
+        *self.flexes.lock() = flexes;
+        Ok(())
+    }
+
+    /// Inserts `member` before the member at `index`, or last if `index` is the member
+    /// count, with `flex` relative to the existing flexes. All flexes are then normalized.
+    pub fn insert_at(&mut self, index: usize, member: Member, flex: f32) -> Result<()> {
+        if index > self.members.len() {
+            return Err(anyhow!(
+                "Index {index} is out of bounds for an axis with {} members",
+                self.members.len()
+            ));
         }
+        self.members.insert(index, member);
+        self.bounding_boxes.lock().insert(index, None);
+        let mut flexes = self.flexes.lock();
+        flexes.insert(index, flex);
+        element::normalize_flexes(&mut flexes);
+        Ok(())
     }
 
     fn split(
@@ -332,28 +1578,49 @@ impl PaneAxis {
         old_pane: &View<Pane>,
         new_pane: &View<Pane>,
         direction: SplitDirection,
-    ) -> Result<()> {
-        for (mut idx, member) in self.members.iter_mut().enumerate() {
+        ratio: Option<f32>,
+    ) -> Result<SplitOutcome> {
+        for (idx, member) in self.members.iter_mut().enumerate() {
             match member {
                 Member::Axis(axis) => {
-                    if axis.split(old_pane, new_pane, direction).is_ok() {
-                        return Ok(());
+                    if let Ok(outcome) = axis.split(old_pane, new_pane, direction, ratio) {
+                        return Ok(outcome);
                     }
                 }
                 Member::Pane(pane) => {
                     if pane == old_pane {
                         if direction.axis() == self.axis {
-                            if direction.increasing() {
-                                idx += 1;
-                            }
+                            let (old_ix, new_ix) = if direction.increasing() {
+                                (idx, idx + 1)
+                            } else {
+                                (idx + 1, idx)
+                            };
 
-                            self.members.insert(idx, Member::Pane(new_pane.clone()));
-                            *self.flexes.lock() = vec![1.; self.members.len()];
+                            self.members.insert(new_ix, Member::Pane(new_pane.clone()));
+                            self.bounding_boxes.lock().insert(new_ix, None);
+                            let old_flexes = {
+                                let mut flexes = self.flexes.lock();
+                                let old_flexes = flexes.clone();
+                                if let Some(ratio) = ratio {
+                                    let old_flex = flexes[idx];
+                                    flexes.insert(new_ix, old_flex * ratio);
+                                    flexes[old_ix] = old_flex * (1. - ratio);
+                                } else {
+                                    *flexes = vec![1.; self.members.len()];
+                                }
+                                old_flexes
+                            };
+                            self.animate_insertion(new_ix, &old_flexes);
+                            return Ok(SplitOutcome::ExtendedAxis { index: new_ix });
                         } else {
-                            *member =
-                                Member::new_axis(old_pane.clone(), new_pane.clone(), direction);
+                            *member = Member::new_axis(
+                                old_pane.clone(),
+                                new_pane.clone(),
+                                direction,
+                                ratio,
+                            );
+                            return Ok(SplitOutcome::CreatedAxis);
                         }
-                        return Ok(());
                     }
                 }
             }
@@ -388,7 +1655,21 @@ impl PaneAxis {
         if found_pane {
             if let Some(idx) = remove_member {
                 self.members.remove(idx);
-                *self.flexes.lock() = vec![1.; self.members.len()];
+                self.bounding_boxes.lock().remove(idx);
+                let mut from = {
+                    let mut flexes = self.flexes.lock();
+                    let from = flexes.clone();
+                    flexes.remove(idx);
+                    element::normalize_flexes(&mut flexes);
+                    from
+                };
+                // The neighbor that takes focus fills the removed member's space at first,
+                // then hands the excess out to the others as the flexes settle.
+                let removed_flex = from.remove(idx);
+                if let Some(neighbor) = from.get_mut(idx.saturating_sub(1)) {
+                    *neighbor += removed_flex;
+                }
+                self.animate_from(from);
             }
 
             if self.members.len() == 1 {
@@ -403,6 +1684,72 @@ impl PaneAxis {
         }
     }
 
+    fn flatten(&mut self) {
+        for member in &mut self.members {
+            if let Member::Axis(axis) = member {
+                axis.flatten();
+            }
+        }
+        if !self
+            .members
+            .iter()
+            .any(|member| matches!(member, Member::Axis(axis) if axis.axis == self.axis))
+        {
+            return;
+        }
+
+        let old_flexes = self.flexes.lock().clone();
+        let mut members = Vec::new();
+        let mut flexes = Vec::new();
+        for (member, flex) in self.members.drain(..).zip(old_flexes) {
+            match member {
+                Member::Axis(axis) if axis.axis == self.axis => {
+                    let child_len = axis.members.len() as f32;
+                    flexes.extend(
+                        axis.flexes
+                            .lock()
+                            .iter()
+                            .map(|child_flex| flex * child_flex / child_len),
+                    );
+                    members.extend(axis.members);
+                }
+                member => {
+                    members.push(member);
+                    flexes.push(flex);
+                }
+            }
+        }
+        element::normalize_flexes(&mut flexes);
+
+        self.members = members;
+        *self.flexes.lock() = flexes;
+        *self.bounding_boxes.lock() = vec![None; self.members.len()];
+    }
+
+    fn find_parent_axis(&self, pane: &View<Pane>) -> Option<(&PaneAxis, usize)> {
+        self.members
+            .iter()
+            .enumerate()
+            .find_map(|(idx, member)| match member {
+                Member::Axis(axis) => axis.find_parent_axis(pane),
+                Member::Pane(member) => (member == pane).then_some((self, idx)),
+            })
+    }
+
+    fn removal_neighbor(&self, pane_to_remove: &View<Pane>) -> Option<View<Pane>> {
+        let (parent, idx) = self.find_parent_axis(pane_to_remove)?;
+        let (neighbor_idx, direction) = match (idx, parent.axis) {
+            (0, Axis::Horizontal) => (1, SplitDirection::Right),
+            (0, Axis::Vertical) => (1, SplitDirection::Down),
+            (_, Axis::Horizontal) => (idx - 1, SplitDirection::Left),
+            (_, Axis::Vertical) => (idx - 1, SplitDirection::Up),
+        };
+        parent
+            .members
+            .get(neighbor_idx)
+            .map(|member| member.entry_pane(direction))
+    }
+
     fn swap(&mut self, from: &View<Pane>, to: &View<Pane>) {
         for member in self.members.iter_mut() {
             match member {
@@ -418,6 +1765,133 @@ impl PaneAxis {
         }
     }
 
+    /// Whether one more pane can be split off in `direction` without any pane ending up below
+    /// its minimum size, given `available` pixels along `direction`'s axis. Along this axis the
+    /// new pane joins the members, which then share the space. Across it, the new pane shares
+    /// the space with just the member it's split from, so it's enough for one member to fit.
+    pub fn can_split(&self, available: f32, direction: SplitDirection, cx: &AppContext) -> bool {
+        let axis = direction.axis();
+        let available = px(available);
+        let new_pane_size = min_pane_size(axis, cx);
+        if axis == self.axis {
+            let members_size = self
+                .members
+                .iter()
+                .fold(px(0.), |total, member| total + member.min_size(axis, cx));
+            members_size + new_pane_size <= available
+        } else {
+            self.members
+                .iter()
+                .any(|member| member.min_size(axis, cx) + new_pane_size <= available)
+        }
+    }
+
+    pub fn rotate(&mut self) {
+        self.axis = self.axis.invert();
+    }
+
+    fn axis_containing_mut(&mut self, pane: &View<Pane>) -> Option<&mut PaneAxis> {
+        if self
+            .members
+            .iter()
+            .any(|member| matches!(member, Member::Pane(member) if member == pane))
+        {
+            return Some(self);
+        }
+
+        self.members.iter_mut().find_map(|member| match member {
+            Member::Axis(axis) => axis.axis_containing_mut(pane),
+            Member::Pane(_) => None,
+        })
+    }
+
+    /// Returns true once an axis containing `pane` has been resized.
+    fn resize_containing(
+        &self,
+        pane: &View<Pane>,
+        direction: SplitDirection,
+        amount: f32,
+        cx: &AppContext,
+    ) -> bool {
+        let Some(ix) = self.members.iter().position(|member| member.contains(pane)) else {
+            return false;
+        };
+        if let Member::Axis(axis) = &self.members[ix] {
+            if axis.resize_containing(pane, direction, amount, cx) {
+                return true;
+            }
+        }
+        if self.axis != direction.axis() {
+            return false;
+        }
+
+        // The handle being moved sits between `handle_ix` and the member after it.
+        let handle_ix = if direction.increasing() {
+            if ix + 1 >= self.members.len() {
+                return false;
+            }
+            ix
+        } else if let Some(handle_ix) = ix.checked_sub(1) {
+            handle_ix
+        } else {
+            return false;
+        };
+
+        // Without a layout there's no pixel size to clamp against.
+        let laid_out_size = self
+            .bounding_boxes
+            .lock()
+            .iter()
+            .try_fold(px(0.), |total, bounds| {
+                Some(total + bounds.as_ref()?.size.along(self.axis))
+            });
+        let (container_size, min_sizes) = match laid_out_size {
+            Some(container_size) => (
+                container_size,
+                self.members
+                    .iter()
+                    .map(|member| member.min_size(self.axis, cx))
+                    .collect(),
+            ),
+            None => (
+                px(self.members.len() as f32),
+                vec![px(0.); self.members.len()],
+            ),
+        };
+
+        let mut flexes = self.flexes.lock();
+        let size = container_size * (flexes[handle_ix] / flexes.len() as f32);
+        let change = container_size * amount;
+        let target_size = if direction.increasing() {
+            size + change
+        } else {
+            size - change
+        };
+        element::resize_flexes(
+            &mut flexes,
+            handle_ix,
+            target_size,
+            container_size,
+            &min_sizes,
+        );
+        element::normalize_flexes(&mut flexes);
+        true
+    }
+
+    /// Rescales this axis' flexes to sum to its member count, preserving their ratios.
+    pub fn normalize_flexes(&self) {
+        element::normalize_flexes(self.flexes.lock().as_mut_slice());
+    }
+
+    /// The area the members took up when the axis was last painted, or `None` if any of
+    /// them hasn't been painted.
+    fn painted_bounds(&self) -> Option<Bounds<Pixels>> {
+        let bounding_boxes = self.bounding_boxes.lock();
+        let mut bounding_boxes = bounding_boxes.iter().copied();
+        let first = bounding_boxes.next()??;
+        bounding_boxes.try_fold(first, |painted, bounds| Some(painted.union(&bounds?)))
+    }
+
     fn bounding_box_for_pane(&self, pane: &View<Pane>) -> Option<Bounds<Pixels>> {
         debug_assert!(self.members.len() == self.bounding_boxes.lock().len());
 
@@ -438,6 +1912,20 @@ impl PaneAxis {
         None
     }
 
+    fn collect_pane_bounds(&self, pane_bounds: &mut Vec<(View<Pane>, Bounds<Pixels>)>) {
+        let bounding_boxes = self.bounding_boxes.lock();
+        for (member, bounds) in self.members.iter().zip(bounding_boxes.iter()) {
+            match member {
+                Member::Pane(pane) => {
+                    if let Some(bounds) = bounds {
+                        pane_bounds.push((pane.clone(), *bounds));
+                    }
+                }
+                Member::Axis(axis) => axis.collect_pane_bounds(pane_bounds),
+            }
+        }
+    }
+
     fn pane_at_pixel_position(&self, coordinate: Point<Pixels>) -> Option<&View<Pane>> {
         debug_assert!(self.members.len() == self.bounding_boxes.lock().len());
 
@@ -464,19 +1952,45 @@ impl PaneAxis {
         active_call: Option<&Model<ActiveCall>>,
         active_pane: &View<Pane>,
         zoomed: Option<&AnyWeakView>,
+        minimized: &HashMap<View<Pane>, f32>,
         app_state: &Arc<AppState>,
         cx: &mut ViewContext<Workspace>,
     ) -> gpui::AnyElement {
         debug_assert!(self.members.len() == self.flexes.lock().len());
         let mut active_pane_ix = None;
+        let min_sizes = self
+            .members
+            .iter()
+            .map(|member| member.min_size(self.axis, cx))
+            .collect();
+        let minimized_members = self
+            .members
+            .iter()
+            .map(|member| matches!(member, Member::Pane(pane) if minimized.contains_key(pane)))
+            .collect();
+        let divider_focus = {
+            let mut divider_focus = self.divider_focus.lock();
+            let divider_count = self.members.len().saturating_sub(1);
+            divider_focus.truncate(divider_count);
+            while divider_focus.len() < divider_count {
+                divider_focus.push(cx.focus_handle());
+            }
+            divider_focus.iter().cloned().collect()
+        };
 
         pane_axis(
             self.axis,
             basis,
             self.flexes.clone(),
             self.bounding_boxes.clone(),
+            self.scroll_offset.clone(),
+            self.animation.clone(),
             cx.view().downgrade(),
         )
+        .with_min_sizes(min_sizes)
+        .with_minimized(minimized_members)
+        .with_divider_focus(divider_focus)
+        .with_hovered_handle(*self.hovered_handle.lock())
         .children(self.members.iter().enumerate().map(|(ix, member)| {
             if member.contains(active_pane) {
                 active_pane_ix = Some(ix);
@@ -489,16 +2003,80 @@ impl PaneAxis {
                     active_call,
                     active_pane,
                     zoomed,
+                    minimized,
                     app_state,
                     cx,
                 )
                 .into_any_element()
         }))
         .with_active_pane(active_pane_ix)
+        .with_gap(WorkspaceSettings::get_global(cx).pane_gap)
+        .with_resizable(WorkspaceSettings::get_global(cx).resizable_panes)
+        .with_scroll_on_overflow(WorkspaceSettings::get_global(cx).scroll_panes_on_overflow)
         .into_any_element()
     }
 }
 
+/// The panes that changed between two layouts, as computed by [`PaneGroup::diff`]. Each
+/// list is in the order [`PaneGroup::panes`] visits the layout the panes are found in.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LayoutDiff {
+    pub added: Vec<View<Pane>>,
+    pub removed: Vec<View<Pane>>,
+    /// Panes in both layouts that ended up in a different axis, for example because their
+    /// old one was split or flattened away. An axis is told apart by the panes under it that
+    /// are in both layouts, so moving within the same axis, or adding and removing its other
+    /// panes, doesn't count.
+    pub reparented: Vec<View<Pane>>,
+}
+
+impl LayoutDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.reparented.is_empty()
+    }
+}
+
+/// How [`PaneGroup::split_returning_axis`] fit the new pane into the tree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplitOutcome {
+    /// The new pane was inserted at `index` of the axis already containing the split pane.
+    ExtendedAxis { index: usize },
+    /// The split pane was replaced by a new axis holding it and the new pane.
+    CreatedAxis,
+}
+
+/// Emitted by the [`Workspace`] when its center pane group changes shape.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PaneGroupEvent {
+    Split,
+    Removed,
+    /// Emitted once per resize, when the user lets go of a handle rather than on every move.
+    Resized,
+    /// Emitted when an axis flips between horizontal and vertical.
+    Rotated,
+}
+
+/// What the context menu of a resize handle can do to the axis the handle belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HandleAction {
+    /// Gives every member of the axis the same size.
+    Equalize,
+    /// Gives the two members on either side of the handle the same size, like a double click.
+    Reset,
+    /// Flips the axis between horizontal and vertical.
+    RotateAxis,
+}
+
+/// The context menu opened by right-clicking a resize handle.
+pub(crate) struct HandleMenu {
+    pub(crate) menu: View<ContextMenu>,
+    pub(crate) position: Point<Pixels>,
+    /// The flexes of the axis the handle belongs to, which identify the axis.
+    pub(crate) flexes: Arc<Mutex<Vec<f32>>>,
+    pub(crate) ix: usize,
+    pub(crate) _subscription: Subscription,
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
 pub enum SplitDirection {
     Up,
@@ -521,6 +2099,32 @@ impl SplitDirection {
         }
     }
 
+    /// The edge of `bounds` whose drop zone, the outer quarter of `bounds` on that side,
+    /// contains `position`.
+    pub fn for_drop_position(bounds: Bounds<Pixels>, position: Point<Pixels>) -> Option<Self> {
+        Self::all().into_iter().find(|direction| {
+            direction
+                .along_edge_fraction(bounds, 0.25)
+                .contains(&position)
+        })
+    }
+
+    /// The direction `delta` mostly points in. Equal components, including a zero vector,
+    /// count as horizontal, and a zero component counts as positive.
+    pub fn from_vector(delta: Point<Pixels>) -> Self {
+        if delta.x.abs() >= delta.y.abs() {
+            if delta.x < px(0.) {
+                Self::Left
+            } else {
+                Self::Right
+            }
+        } else if delta.y < px(0.) {
+            Self::Up
+        } else {
+            Self::Down
+        }
+    }
+
     pub fn along_edge(&self, bounds: Bounds<Pixels>, length: Pixels) -> Bounds<Pixels> {
         match self {
             Self::Up => Bounds {
@@ -536,12 +2140,19 @@ impl SplitDirection {
                 size: size(length, bounds.size.height),
             },
             Self::Right => Bounds {
-                origin: point(bounds.lower_right().x - length, bounds.lower_left().y),
+                origin: point(bounds.upper_right().x - length, bounds.origin.y),
                 size: size(length, bounds.size.height),
             },
         }
     }
 
+    /// Like [`Self::along_edge`], with the length given as a fraction of `bounds` along this
+    /// direction's axis. The fraction is clamped to `0.0..=1.0`.
+    pub fn along_edge_fraction(&self, bounds: Bounds<Pixels>, fraction: f32) -> Bounds<Pixels> {
+        let length = bounds.size.along(self.axis()) * fraction.clamp(0., 1.);
+        self.along_edge(bounds, length)
+    }
+
     pub fn axis(&self) -> Axis {
         match self {
             Self::Up | Self::Down => Axis::Vertical,
@@ -555,36 +2166,198 @@ impl SplitDirection {
             Self::Down | Self::Right => true,
         }
     }
-}
-
-mod element {
 
-    use std::{cell::RefCell, iter, rc::Rc, sync::Arc};
+    pub fn opposite(&self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
 
-    use gpui::{
-        px, relative, Along, AnyElement, Axis, Bounds, CursorStyle, Element, InteractiveBounds,
-        IntoElement, MouseDownEvent, MouseMoveEvent, MouseUpEvent, ParentElement, Pixels, Point,
-        Size, Style, WeakView, WindowContext,
-    };
-    use parking_lot::Mutex;
-    use settings::Settings;
-    use smallvec::SmallVec;
-    use ui::prelude::*;
-    use util::ResultExt;
+    pub fn rotate_clockwise(&self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+        }
+    }
+}
 
-    use crate::Workspace;
+fn min_pane_size(axis: Axis, cx: &AppContext) -> Pixels {
+    let settings = WorkspaceSettings::get_global(cx);
+    px(match axis {
+        Axis::Horizontal => settings.pane_min_width,
+        Axis::Vertical => settings.pane_min_height,
+    })
+}
 
-    use crate::WorkspaceSettings;
+/// Names panes `a` through `z`, then `aa`, `ab`, and so on.
+fn layout_pane_name(ix: usize) -> String {
+    let mut name = Vec::new();
+    let mut remaining = ix + 1;
+    while remaining > 0 {
+        remaining -= 1;
+        name.push(b'a' + (remaining % 26) as u8);
+        remaining /= 26;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap()
+}
 
-    use super::{HANDLE_HITBOX_SIZE, HORIZONTAL_MIN_SIZE, VERTICAL_MIN_SIZE};
+struct LayoutParser<'a, F> {
+    layout: &'a str,
+    position: usize,
+    resolve: F,
+    seen: HashSet<&'a str>,
+}
 
-    const DIVIDER_SIZE: f32 = 1.0;
+impl<'a, F: FnMut(&str) -> Option<View<Pane>>> LayoutParser<'a, F> {
+    fn member(&mut self) -> Result<Member> {
+        self.skip_whitespace();
+        let start = self.position;
+        let name = self.name()?;
+        self.skip_whitespace();
+        if self.peek() != Some('[') {
+            if !self.seen.insert(name) {
+                return Err(anyhow!("Pane {name:?} appears more than once"));
+            }
+            let pane = (self.resolve)(name).ok_or_else(|| anyhow!("Unknown pane {name:?}"))?;
+            return Ok(Member::Pane(pane));
+        }
 
-    pub(super) fn pane_axis(
-        axis: Axis,
-        basis: usize,
-        flexes: Arc<Mutex<Vec<f32>>>,
-        bounding_boxes: Arc<Mutex<Vec<Option<Bounds<Pixels>>>>>,
+        let axis = match name {
+            "h" => Axis::Horizontal,
+            "v" => Axis::Vertical,
+            _ => {
+                return Err(anyhow!(
+                    "Unknown axis {name:?} at {start}, expected 'h' or 'v'"
+                ))
+            }
+        };
+        self.position += 1;
+        let mut pane_axis = PaneAxis::new(axis, vec![self.member()?]);
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => {
+                    self.position += 1;
+                    pane_axis.insert_at(pane_axis.members.len(), self.member()?, 1.)?;
+                }
+                Some(']') => {
+                    self.position += 1;
+                    break;
+                }
+                Some(c) => {
+                    return Err(anyhow!(
+                        "Unexpected '{c}' at {}, expected ',' or ']'",
+                        self.position
+                    ))
+                }
+                None => return Err(anyhow!("Unclosed axis starting at {start}")),
+            }
+        }
+        if pane_axis.members.len() < 2 {
+            return Err(anyhow!("Axis at {start} has fewer than two members"));
+        }
+        Ok(Member::Axis(pane_axis))
+    }
+
+    fn name(&mut self) -> Result<&'a str> {
+        let start = self.position;
+        let rest = &self.layout[start..];
+        let len = rest
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(match self.peek() {
+                Some(c) => anyhow!("Unexpected '{c}' at {start}, expected a pane or an axis"),
+                None => anyhow!("Layout ended early, expected a pane or an axis"),
+            });
+        }
+        self.position += len;
+        Ok(&rest[..len])
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.layout[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.layout[self.position..].chars().next()
+    }
+}
+
+mod element {
+
+    use std::{
+        cell::RefCell,
+        rc::Rc,
+        sync::{Arc, Once},
+        time::Duration,
+    };
+
+    use gpui::{
+        px, relative, size, Along, AnyElement, AvailableSpace, Axis, Bounds, ContentMask,
+        CursorStyle, Element, FocusHandle, Hsla, InteractiveBounds, IntoElement, KeyDownEvent,
+        MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, ParentElement, Pixels, Point,
+        ScrollWheelEvent, Style, View, WeakView, WindowContext,
+    };
+    use parking_lot::Mutex;
+    use settings::Settings;
+    use smallvec::SmallVec;
+    use theme::ThemeColors;
+    use ui::{prelude::*, Tooltip};
+    use util::ResultExt;
+
+    use crate::{pane_group::FlexAnimation, PaneGroupEvent, Workspace};
+
+    use crate::{ScrollResizeModifier, WorkspaceSettings};
+
+    const DIVIDER_SIZE: f32 = 1.0;
+    pub(super) const MIN_FLEX: f32 = 0.01;
+    const FALLBACK_CHILD_SIZE: f32 = 240.;
+    /// The smallest any child is laid out, whatever the settings, so it stays visible.
+    const MIN_CHILD_SIZE: f32 = 16.;
+    /// How far one modifier-scroll or arrow key press moves a handle.
+    const RESIZE_STEP: f32 = 10.;
+    /// How long members take to settle into place after a split or removal.
+    const FLEX_ANIMATION_DURATION: Duration = Duration::from_millis(150);
+    /// How far the mouse has to move after pressing a handle before the drag resizes anything,
+    /// so that a slightly imprecise click leaves the layout alone.
+    const DRAG_THRESHOLD: f32 = 3.;
+    /// The smallest active pane magnification that's applied, whatever the settings.
+    const MIN_MAGNIFICATION: f32 = 0.1;
+
+    /// The handle of an axis that's being dragged, if any.
+    #[derive(Default)]
+    pub struct DragState {
+        handle: Option<usize>,
+        /// Where the mouse went down on the handle, until it moves past [`DRAG_THRESHOLD`].
+        pending_from: Option<Point<Pixels>>,
+        /// The split percentages tooltip of the handle that was last moved. It's kept up to
+        /// date by the event handlers that move the handle, and painted while the handle is
+        /// dragged or focused.
+        tooltip: Option<HandleTooltip>,
+    }
+
+    struct HandleTooltip {
+        ix: usize,
+        dragging: bool,
+        percentages: String,
+        view: View<Tooltip>,
+    }
+
+    pub(super) fn pane_axis(
+        axis: Axis,
+        basis: usize,
+        flexes: Arc<Mutex<Vec<f32>>>,
+        bounding_boxes: Arc<Mutex<Vec<Option<Bounds<Pixels>>>>>,
+        scroll_offset: Arc<Mutex<Pixels>>,
+        animation: Arc<Mutex<Option<FlexAnimation>>>,
         workspace: WeakView<Workspace>,
     ) -> PaneAxisElement {
         PaneAxisElement {
@@ -592,8 +2365,17 @@ mod element {
             basis,
             flexes,
             bounding_boxes,
+            scroll_offset,
+            animation,
             children: SmallVec::new(),
+            min_sizes: SmallVec::new(),
+            minimized: SmallVec::new(),
+            divider_focus: SmallVec::new(),
             active_pane_ix: None,
+            hovered_handle: None,
+            gap: 0.,
+            resizable: true,
+            scroll_on_overflow: false,
             workspace,
         }
     }
@@ -603,8 +2385,17 @@ mod element {
         basis: usize,
         flexes: Arc<Mutex<Vec<f32>>>,
         bounding_boxes: Arc<Mutex<Vec<Option<Bounds<Pixels>>>>>,
+        scroll_offset: Arc<Mutex<Pixels>>,
+        animation: Arc<Mutex<Option<FlexAnimation>>>,
         children: SmallVec<[AnyElement; 2]>,
+        min_sizes: SmallVec<[Pixels; 2]>,
+        minimized: SmallVec<[bool; 2]>,
+        divider_focus: SmallVec<[FocusHandle; 2]>,
         active_pane_ix: Option<usize>,
+        hovered_handle: Option<usize>,
+        gap: f32,
+        resizable: bool,
+        scroll_on_overflow: bool,
         workspace: WeakView<Workspace>,
     }
 
@@ -614,148 +2405,264 @@ mod element {
             self
         }
 
+        /// The minimum length of each child along the axis, in child order. Children without
+        /// one, and any shorter than the axis-wide minimum from the settings, use the latter.
+        pub fn with_min_sizes(mut self, min_sizes: SmallVec<[Pixels; 2]>) -> Self {
+            self.min_sizes = min_sizes;
+            self
+        }
+
+        /// Whether each child is a minimized pane, in child order. Minimized children keep
+        /// their thin strip rather than being grown to a minimum size.
+        pub fn with_minimized(mut self, minimized: SmallVec<[bool; 2]>) -> Self {
+            self.minimized = minimized;
+            self
+        }
+
+        /// The focus handle of each handle, in order. A focused handle can be moved with the
+        /// arrow keys along the axis.
+        pub fn with_divider_focus(mut self, divider_focus: SmallVec<[FocusHandle; 2]>) -> Self {
+            self.divider_focus = divider_focus;
+            self
+        }
+
+        /// The space, in pixels, left between neighboring children. It's taken out of the
+        /// space shared between the children, and the handles are centered on it.
+        pub fn with_gap(mut self, gap: f32) -> Self {
+            self.gap = gap;
+            self
+        }
+
+        /// The handle that shows as hovered when the mouse is over it. Other handles under
+        /// the mouse leave it to a handle in another axis that overlaps them.
+        pub fn with_hovered_handle(mut self, hovered_handle: Option<usize>) -> Self {
+            self.hovered_handle = hovered_handle;
+            self
+        }
+
+        /// When false, the handles between children are drawn but can't be dragged.
+        pub fn with_resizable(mut self, resizable: bool) -> Self {
+            self.resizable = resizable;
+            self
+        }
+
+        /// When true, children that can't all fit at the minimum size keep it and can be
+        /// scrolled through, instead of being squeezed to fit.
+        pub fn with_scroll_on_overflow(mut self, scroll_on_overflow: bool) -> Self {
+            self.scroll_on_overflow = scroll_on_overflow;
+            self
+        }
+
         fn compute_resize(
             flexes: &Arc<Mutex<Vec<f32>>>,
-            e: &MouseMoveEvent,
             ix: usize,
-            axis: Axis,
-            child_start: Point<Pixels>,
-            container_size: Size<Pixels>,
-            workspace: WeakView<Workspace>,
+            target_size: Pixels,
+            container_size: Pixels,
+            min_sizes: &[Pixels],
+            active_pane_ix: Option<usize>,
+            magnification: f32,
             cx: &mut WindowContext,
-        ) {
-            let min_size = match axis {
-                Axis::Horizontal => px(HORIZONTAL_MIN_SIZE),
-                Axis::Vertical => px(VERTICAL_MIN_SIZE),
-            };
+        ) -> bool {
             let mut flexes = flexes.lock();
             debug_assert!(flex_values_in_bounds(flexes.as_slice()));
 
-            let size = move |ix, flexes: &[f32]| {
-                container_size.along(axis) * (flexes[ix] / flexes.len() as f32)
-            };
-
-            // Don't allow resizing to less than the minimum size, if elements are already too small
-            if min_size - px(1.) > size(ix, flexes.as_slice()) {
-                return;
+            // The handles sit between the magnified children, so resize in that space and
+            // then map the result back onto the stored flexes.
+            let mut layout_flexes = magnified_flexes(&flexes, active_pane_ix, magnification);
+            if !resize_flexes(
+                &mut layout_flexes,
+                ix,
+                target_size,
+                container_size,
+                min_sizes,
+            ) {
+                return false;
             }
+            *flexes = unmagnified_flexes(&layout_flexes, active_pane_ix, magnification);
+            normalize_flexes(&mut flexes);
 
-            let mut proposed_current_pixel_change =
-                (e.position - child_start).along(axis) - size(ix, flexes.as_slice());
+            cx.stop_propagation();
+            cx.refresh();
+            true
+        }
 
-            let flex_changes = |pixel_dx, target_ix, next: isize, flexes: &[f32]| {
-                let flex_change = pixel_dx / container_size.along(axis);
-                let current_target_flex = flexes[target_ix] + flex_change;
-                let next_target_flex = flexes[(target_ix as isize + next) as usize] - flex_change;
-                (current_target_flex, next_target_flex)
+        /// Points the tooltip in `state` at handle `ix`, showing the split percentages the
+        /// children on either side of it are laid out at. A focused handle's tooltip also says
+        /// what it is. The view is reused while it's for the same handle, and only updated
+        /// when the percentages change.
+        fn update_handle_tooltip(
+            state: &mut DragState,
+            flexes: &Mutex<Vec<f32>>,
+            ix: usize,
+            container_size: Pixels,
+            min_sizes: &[Pixels],
+            active_pane_ix: Option<usize>,
+            magnification: f32,
+            dragging: bool,
+            cx: &mut WindowContext,
+        ) {
+            let layout_flexes = fit_to_min_size(
+                &magnified_flexes(&flexes.lock(), active_pane_ix, magnification),
+                container_size,
+                min_sizes,
+            );
+            let percentages = split_percentages(&layout_flexes, ix);
+            let tooltip = move |percentages: String| {
+                if dragging {
+                    Tooltip::new(percentages)
+                } else {
+                    Tooltip::new(divider_label(ix)).meta(percentages)
+                }
             };
 
-            let mut successors = iter::from_fn({
-                let forward = proposed_current_pixel_change > px(0.);
-                let mut ix_offset = 0;
-                let len = flexes.len();
-                move || {
-                    let result = if forward {
-                        (ix + 1 + ix_offset < len).then(|| ix + ix_offset)
-                    } else {
-                        (ix as isize - ix_offset as isize >= 0).then(|| ix - ix_offset)
-                    };
-
-                    ix_offset += 1;
-
-                    result
+            match &mut state.tooltip {
+                Some(current) if current.ix == ix && current.dragging == dragging => {
+                    if current.percentages != percentages {
+                        current.percentages = percentages.clone();
+                        current.view.update(cx, |view, cx| {
+                            *view = tooltip(percentages);
+                            cx.notify();
+                        });
+                    }
+                }
+                _ => {
+                    let view = cx.new_view(|_| tooltip(percentages.clone()));
+                    state.tooltip = Some(HandleTooltip {
+                        ix,
+                        dragging,
+                        percentages,
+                        view,
+                    });
                 }
-            });
-
-            while proposed_current_pixel_change.abs() > px(0.) {
-                let Some(current_ix) = successors.next() else {
-                    break;
-                };
-
-                let next_target_size = Pixels::max(
-                    size(current_ix + 1, flexes.as_slice()) - proposed_current_pixel_change,
-                    min_size,
-                );
-
-                let current_target_size = Pixels::max(
-                    size(current_ix, flexes.as_slice()) + size(current_ix + 1, flexes.as_slice())
-                        - next_target_size,
-                    min_size,
-                );
-
-                let current_pixel_change =
-                    current_target_size - size(current_ix, flexes.as_slice());
-
-                let (current_target_flex, next_target_flex) =
-                    flex_changes(current_pixel_change, current_ix, 1, flexes.as_slice());
-
-                flexes[current_ix] = current_target_flex;
-                flexes[current_ix + 1] = next_target_flex;
-
-                proposed_current_pixel_change -= current_pixel_change;
             }
-
-            workspace
-                .update(cx, |this, cx| this.schedule_serialize(cx))
-                .log_err();
-            cx.stop_propagation();
-            cx.refresh();
         }
 
         fn push_handle(
             flexes: Arc<Mutex<Vec<f32>>>,
-            dragged_handle: Rc<RefCell<Option<usize>>>,
+            dragged_handle: Rc<RefCell<DragState>>,
             axis: Axis,
             ix: usize,
             pane_bounds: Bounds<Pixels>,
-            axis_bounds: Bounds<Pixels>,
+            container_size: Pixels,
+            gap: Pixels,
+            handle_size: Pixels,
+            resizable: bool,
+            min_sizes: Rc<[Pixels]>,
+            active_pane_ix: Option<usize>,
+            magnification: f32,
+            scroll_resize_modifier: ScrollResizeModifier,
+            focus_handle: Option<FocusHandle>,
+            claimed: bool,
             workspace: WeakView<Workspace>,
             cx: &mut ElementContext,
         ) {
-            let handle_bounds = Bounds {
+            let handle_bounds = handle_bounds(axis, pane_bounds, handle_size, gap);
+            let divider_bounds = Bounds {
                 origin: pane_bounds.origin.apply_along(axis, |origin| {
-                    origin + pane_bounds.size.along(axis) - px(HANDLE_HITBOX_SIZE / 2.)
+                    origin
+                        + pane_bounds.size.along(axis)
+                        + Pixels::max(gap - px(DIVIDER_SIZE), px(0.)) / 2.
                 }),
-                size: pane_bounds
-                    .size
-                    .apply_along(axis, |_| px(HANDLE_HITBOX_SIZE)),
-            };
-            let divider_bounds = Bounds {
-                origin: pane_bounds
-                    .origin
-                    .apply_along(axis, |origin| origin + pane_bounds.size.along(axis)),
                 size: pane_bounds.size.apply_along(axis, |_| px(DIVIDER_SIZE)),
             };
 
             cx.with_z_index(3, |cx| {
+                cx.paint_quad(gpui::fill(
+                    divider_bounds,
+                    divider_color(axis, cx.theme().colors()),
+                ));
+                if !resizable {
+                    return;
+                }
+
                 let interactive_handle_bounds = InteractiveBounds {
                     bounds: handle_bounds,
                     stacking_order: cx.stacking_order().clone(),
                 };
-                if interactive_handle_bounds.visibly_contains(&cx.mouse_position(), cx) {
+                let mouse_position = cx.mouse_position();
+                let contains_mouse = handle_bounds.contains(&mouse_position);
+                let hovered =
+                    claimed && interactive_handle_bounds.visibly_contains(&mouse_position, cx);
+                if hovered {
                     cx.set_cursor_style(match axis {
                         Axis::Vertical => CursorStyle::ResizeUpDown,
                         Axis::Horizontal => CursorStyle::ResizeLeftRight,
                     })
                 }
 
-                cx.add_opaque_layer(handle_bounds);
-                cx.paint_quad(gpui::fill(divider_bounds, cx.theme().colors().border));
+                // Let the handle that claimed the mouse show through where this one overlaps it
+                if claimed || !contains_mouse {
+                    cx.add_opaque_layer(handle_bounds);
+                }
+                let focused = focus_handle
+                    .as_ref()
+                    .map_or(false, |focus_handle| focus_handle.is_focused(cx));
+                if hovered || focused || dragged_handle.borrow().handle == Some(ix) {
+                    cx.paint_quad(gpui::fill(
+                        handle_bounds,
+                        cx.theme().colors().border_focused,
+                    ));
+                }
+
+                cx.on_mouse_event(move |e: &MouseMoveEvent, phase, cx| {
+                    if phase.bubble() && handle_bounds.contains(&e.position) != contains_mouse {
+                        cx.refresh();
+                    }
+                });
+
+                // Whether this handle, rather than one it overlaps, should respond to the mouse
+                let claims = {
+                    let flexes = flexes.clone();
+                    let workspace = workspace.clone();
+                    Rc::new(move |position: Point<Pixels>, cx: &mut ElementContext| {
+                        handle_bounds.contains(&position)
+                            && workspace
+                                .update(cx, |workspace, cx| {
+                                    workspace.center.claims_handle(&flexes, ix, position, cx)
+                                })
+                                .unwrap_or(true)
+                    })
+                };
 
                 cx.on_mouse_event({
                     let dragged_handle = dragged_handle.clone();
                     let flexes = flexes.clone();
                     let workspace = workspace.clone();
+                    let claims = claims.clone();
                     move |e: &MouseDownEvent, phase, cx| {
-                        if phase.bubble() && handle_bounds.contains(&e.position) {
-                            dragged_handle.replace(Some(ix));
-                            if e.click_count >= 2 {
-                                let mut borrow = flexes.lock();
-                                *borrow = vec![1.; borrow.len()];
+                        if phase.bubble() && claims(e.position, cx) {
+                            if e.button == MouseButton::Right {
+                                let flexes = flexes.clone();
                                 workspace
-                                    .update(cx, |this, cx| this.schedule_serialize(cx))
+                                    .update(cx, |workspace, cx| {
+                                        workspace
+                                            .deploy_pane_handle_menu(flexes, ix, e.position, cx)
+                                    })
                                     .log_err();
-
+                                cx.stop_propagation();
+                                return;
+                            }
+                            let was_dragging = dragged_handle
+                                .replace(DragState {
+                                    handle: Some(ix),
+                                    pending_from: Some(e.position),
+                                    tooltip: None,
+                                })
+                                .handle
+                                .is_some();
+                            let equalize = e.click_count >= 2 && !was_dragging;
+                            if equalize {
+                                equalize_adjacent(flexes.lock().as_mut_slice(), ix);
+                            }
+                            workspace
+                                .update(cx, |workspace, cx| {
+                                    workspace.set_resizing_panes(true, cx);
+                                    if equalize {
+                                        cx.emit(PaneGroupEvent::Resized);
+                                    }
+                                })
+                                .log_err();
+                            if equalize {
                                 cx.refresh();
                             }
                             cx.stop_propagation();
@@ -763,22 +2670,119 @@ mod element {
                     }
                 });
                 cx.on_mouse_event({
+                    let flexes = flexes.clone();
+                    let min_sizes = min_sizes.clone();
                     let workspace = workspace.clone();
-                    move |e: &MouseMoveEvent, phase, cx| {
-                        let dragged_handle = dragged_handle.borrow();
-
-                        if phase.bubble() && *dragged_handle == Some(ix) {
-                            Self::compute_resize(
+                    move |e: &ScrollWheelEvent, phase, cx| {
+                        if phase.bubble()
+                            && scroll_resize_modifier.is_held(&e.modifiers)
+                            && claims(e.position, cx)
+                        {
+                            let step = scroll_resize_step(e.delta.pixel_delta(px(20.)));
+                            if Self::compute_resize(
+                                &flexes,
+                                ix,
+                                pane_bounds.size.along(axis) + step,
+                                container_size,
+                                &min_sizes,
+                                active_pane_ix,
+                                magnification,
+                                cx,
+                            ) {
+                                workspace
+                                    .update(cx, |_, cx| cx.emit(PaneGroupEvent::Resized))
+                                    .log_err();
+                            }
+                        }
+                    }
+                });
+                if let Some(focus_handle) = focus_handle {
+                    let flexes = flexes.clone();
+                    let min_sizes = min_sizes.clone();
+                    let dragged_handle = dragged_handle.clone();
+                    cx.with_key_dispatch(None, Some(focus_handle), |_, cx| {
+                        cx.on_key_event(move |e: &KeyDownEvent, phase, cx| {
+                            if !phase.bubble() {
+                                return;
+                            }
+                            if e.keystroke.key == "escape" {
+                                workspace
+                                    .update(cx, |workspace, cx| {
+                                        cx.focus_view(workspace.active_pane())
+                                    })
+                                    .log_err();
+                                cx.stop_propagation();
+                                return;
+                            }
+                            let Some(step) = keyboard_resize_step(axis, &e.keystroke.key) else {
+                                return;
+                            };
+                            if Self::compute_resize(
                                 &flexes,
-                                e,
                                 ix,
-                                axis,
-                                pane_bounds.origin,
-                                axis_bounds.size,
-                                workspace.clone(),
+                                pane_bounds.size.along(axis) + step,
+                                container_size,
+                                &min_sizes,
+                                active_pane_ix,
+                                magnification,
                                 cx,
-                            )
+                            ) {
+                                workspace
+                                    .update(cx, |_, cx| cx.emit(PaneGroupEvent::Resized))
+                                    .log_err();
+                            }
+                            Self::update_handle_tooltip(
+                                &mut dragged_handle.borrow_mut(),
+                                &flexes,
+                                ix,
+                                container_size,
+                                &min_sizes,
+                                active_pane_ix,
+                                magnification,
+                                false,
+                                cx,
+                            );
+                            cx.stop_propagation();
+                        });
+                    });
+                }
+                cx.on_mouse_event(move |e: &MouseMoveEvent, phase, cx| {
+                    let mut dragged_handle = dragged_handle.borrow_mut();
+
+                    if phase.bubble() && dragged_handle.handle == Some(ix) {
+                        if let Some(from) = dragged_handle.pending_from {
+                            if !past_drag_threshold(from, e.position) {
+                                return;
+                            }
+                            dragged_handle.pending_from = None;
                         }
+                        // Keep the middle of the gap under the pointer
+                        let mut target_size =
+                            (e.position - pane_bounds.origin).along(axis) - gap / 2.;
+                        if e.modifiers.shift {
+                            target_size = snap_to_tenth(target_size, container_size);
+                        }
+                        Self::compute_resize(
+                            &flexes,
+                            ix,
+                            target_size,
+                            container_size,
+                            &min_sizes,
+                            active_pane_ix,
+                            magnification,
+                            cx,
+                        );
+                        Self::update_handle_tooltip(
+                            &mut dragged_handle,
+                            &flexes,
+                            ix,
+                            container_size,
+                            &min_sizes,
+                            active_pane_ix,
+                            magnification,
+                            true,
+                            cx,
+                        );
                     }
                 });
             });
@@ -798,7 +2802,7 @@ mod element {
     }
 
     impl Element for PaneAxisElement {
-        type State = Rc<RefCell<Option<usize>>>;
+        type State = Rc<RefCell<DragState>>;
 
         fn request_layout(
             &mut self,
@@ -812,8 +2816,8 @@ mod element {
             style.size.width = relative(1.).into();
             style.size.height = relative(1.).into();
             let layout_id = cx.request_layout(&style, None);
-            let dragged_pane = state.unwrap_or_else(|| Rc::new(RefCell::new(None)));
-            (layout_id, dragged_pane)
+            let dragged_handle = state.unwrap_or_default();
+            (layout_id, dragged_handle)
         }
 
         fn paint(
@@ -822,55 +2826,91 @@ mod element {
             state: &mut Self::State,
             cx: &mut ui::prelude::ElementContext,
         ) {
+            let settings = WorkspaceSettings::get(None, cx);
             let flexes = self.flexes.lock().clone();
             let len = self.children.len();
             debug_assert!(flexes.len() == len);
             debug_assert!(flex_values_in_bounds(flexes.as_slice()));
 
-            let magnification_value = WorkspaceSettings::get(None, cx).active_pane_magnification;
-            let active_pane_magnification = if magnification_value == 1. {
-                None
-            } else {
-                Some(magnification_value)
+            let flexes = {
+                let mut animation = self.animation.lock();
+                let progress = animation
+                    .as_ref()
+                    .filter(|animation| settings.animate_pane_splits && animation.from.len() == len)
+                    .map(|animation| {
+                        animation.started_at.elapsed().as_secs_f32()
+                            / FLEX_ANIMATION_DURATION.as_secs_f32()
+                    });
+                match (animation.as_ref(), progress) {
+                    (Some(animation), Some(progress)) if progress < 1. => {
+                        cx.on_next_frame(|cx| cx.refresh());
+                        interpolate_flexes(&animation.from, &flexes, progress)
+                    }
+                    _ => {
+                        *animation = None;
+                        flexes
+                    }
+                }
             };
 
-            let total_flex = if let Some(flex) = active_pane_magnification {
-                self.children.len() as f32 - 1. + flex
+            let magnification = settings.active_pane_magnification;
+            let handle_size = px(settings.pane_split_handle_size);
+            let min_size = px(match self.axis {
+                Axis::Horizontal => settings.pane_min_width,
+                Axis::Vertical => settings.pane_min_height,
+            });
+            let min_sizes: Rc<[Pixels]> =
+                child_min_sizes(min_size, &self.min_sizes, &self.minimized, len).into();
+            let total_min_size = min_sizes
+                .iter()
+                .fold(px(0.), |total, min_size| total + *min_size);
+
+            let gap = px(self.gap.max(0.));
+            let total_gap = gap * len.saturating_sub(1) as f32;
+            // The space shared between the children, once the gaps between them are left out
+            let container_size = Pixels::max(bounds.size.along(self.axis) - total_gap, px(0.));
+            let overflowing = self.scroll_on_overflow && total_min_size > container_size;
+            let child_sizes = if overflowing {
+                min_sizes.to_vec()
             } else {
-                len as f32
+                let space_per_flex = space_per_flex(container_size, len);
+                fit_to_min_size(
+                    &magnified_flexes(&flexes, self.active_pane_ix, magnification),
+                    container_size,
+                    &min_sizes,
+                )
+                .into_iter()
+                .map(|flex| space_per_flex * flex)
+                .collect()
             };
-
-            let mut origin = bounds.origin;
-            let space_per_flex = bounds.size.along(self.axis) / total_flex;
+            let (child_offsets, scroll_offset) = scrolled_offsets(
+                &child_sizes,
+                bounds.size.along(self.axis),
+                gap,
+                *self.scroll_offset.lock(),
+            );
+            *self.scroll_offset.lock() = scroll_offset;
 
             let mut bounding_boxes = self.bounding_boxes.lock();
             bounding_boxes.clear();
 
-            for (ix, child) in self.children.iter_mut().enumerate() {
-                let child_flex = active_pane_magnification
-                    .map(|magnification| {
-                        if self.active_pane_ix == Some(ix) {
-                            magnification
-                        } else {
-                            1.
-                        }
-                    })
-                    .unwrap_or_else(|| flexes[ix]);
-
-                let child_size = bounds
-                    .size
-                    .apply_along(self.axis, |_| space_per_flex * child_flex);
+            let content_mask = overflowing.then(|| ContentMask { bounds });
+            cx.with_content_mask(content_mask, |cx| {
+                for (ix, child) in self.children.iter_mut().enumerate() {
+                    let origin = bounds
+                        .origin
+                        .apply_along(self.axis, |origin| origin + child_offsets[ix]);
+                    let child_size = bounds.size.apply_along(self.axis, |_| child_sizes[ix]);
 
-                let child_bounds = Bounds {
-                    origin,
-                    size: child_size,
-                };
-                bounding_boxes.push(Some(child_bounds));
-                cx.with_z_index(0, |cx| {
-                    child.draw(origin, child_size.into(), cx);
-                });
+                    let child_bounds = Bounds {
+                        origin,
+                        size: child_size,
+                    };
+                    bounding_boxes.push(Some(child_bounds));
+                    cx.with_z_index(0, |cx| {
+                        child.draw(origin, child_size.into(), cx);
+                    });
 
-                if active_pane_magnification.is_none() {
                     cx.with_z_index(1, |cx| {
                         if ix < len - 1 {
                             Self::push_handle(
@@ -879,23 +2919,96 @@ mod element {
                                 self.axis,
                                 ix,
                                 child_bounds,
-                                bounds,
+                                container_size,
+                                gap,
+                                handle_size,
+                                self.resizable,
+                                min_sizes.clone(),
+                                self.active_pane_ix,
+                                magnification,
+                                settings.scroll_resize_modifier,
+                                self.divider_focus.get(ix).cloned(),
+                                self.hovered_handle == Some(ix),
                                 self.workspace.clone(),
                                 cx,
                             );
                         }
                     });
+
+                    let dragging = state.borrow().handle == Some(ix);
+                    let focused = self
+                        .divider_focus
+                        .get(ix)
+                        .map_or(false, |focus_handle| focus_handle.is_focused(cx));
+                    let tooltip = match &state.borrow().tooltip {
+                        Some(tooltip) if tooltip.ix == ix => Some(tooltip.view.clone()),
+                        _ => None,
+                    };
+                    if let Some(tooltip) = tooltip {
+                        if dragging || focused {
+                            let mut tooltip = tooltip.into_any_element();
+                            let position =
+                                handle_bounds(self.axis, child_bounds, handle_size, gap).center();
+                            cx.with_z_index(4, |cx| {
+                                tooltip.draw(
+                                    position,
+                                    size(AvailableSpace::MinContent, AvailableSpace::MinContent),
+                                    cx,
+                                );
+                            });
+                        } else {
+                            // Its percentages may go stale before the handle is used again
+                            state.borrow_mut().tooltip = None;
+                        }
+                    }
                 }
+            });
 
-                origin = origin.apply_along(self.axis, |val| val + child_size.along(self.axis));
+            if overflowing {
+                let axis = self.axis;
+                let offset = self.scroll_offset.clone();
+                let max_offset = total_min_size - container_size;
+                cx.on_mouse_event(move |e: &ScrollWheelEvent, phase, cx| {
+                    if phase.bubble() && bounds.contains(&e.position) {
+                        let delta = e.delta.pixel_delta(px(20.)).along(axis);
+                        let mut offset = offset.lock();
+                        *offset = Pixels::max(px(0.), Pixels::min(*offset - delta, max_offset));
+                        cx.stop_propagation();
+                        cx.refresh();
+                    }
+                });
             }
 
             cx.with_z_index(1, |cx| {
                 cx.on_mouse_event({
                     let state = state.clone();
-                    move |_: &MouseUpEvent, phase, _cx| {
-                        if phase.bubble() {
-                            state.replace(None);
+                    let flexes = self.flexes.clone();
+                    let workspace = self.workspace.clone();
+                    move |_: &MouseUpEvent, phase, cx| {
+                        if !phase.bubble() {
+                            return;
+                        }
+                        let drag = state.take();
+                        if drag.handle.is_some() {
+                            // A press that never got past the threshold didn't resize anything
+                            let resized = drag.pending_from.is_none();
+                            workspace
+                                .update(cx, |workspace, cx| {
+                                    if resized {
+                                        // Settle any drift left by the drag's many small steps
+                                        if let Some(axis) =
+                                            workspace.center.root.axis_with_flexes_mut(&flexes)
+                                        {
+                                            axis.normalize_flexes();
+                                        }
+                                    }
+                                    workspace.set_resizing_panes(false, cx);
+                                    if resized {
+                                        cx.emit(PaneGroupEvent::Resized);
+                                    }
+                                })
+                                .log_err();
+                            cx.refresh();
                         }
                     }
                 });
@@ -909,7 +3022,2965 @@ mod element {
         }
     }
 
-    fn flex_values_in_bounds(flexes: &[f32]) -> bool {
-        (flexes.iter().copied().sum::<f32>() - flexes.len() as f32).abs() < 0.001
+    /// The flexes to lay children out with `progress` of the way, from 0 to 1, through an
+    /// animation from `from` to `to`. The movement eases out, so it slows as it settles. As
+    /// both sum to the number of children, so does the result.
+    pub(super) fn interpolate_flexes(from: &[f32], to: &[f32], progress: f32) -> Vec<f32> {
+        let t = 1. - (1. - progress.clamp(0., 1.)).powi(3);
+        from.iter()
+            .zip(to)
+            .map(|(from, to)| from + (to - from) * t)
+            .collect()
+    }
+
+    /// Moves the handle after child `ix` so that the child becomes `target_size` long.
+    ///
+    /// Growing the child takes space from the children after the handle, starting with the
+    /// nearest one and cascading further along once it reaches its minimum size. Shrinking it
+    /// works the same way in reverse, cascading into the children before `ix` and handing the
+    /// space to the child after the handle. No child is shrunk below its entry in `min_sizes`.
+    ///
+    /// Returns false if the child is already too small to be resized.
+    pub(super) fn resize_flexes(
+        flexes: &mut Vec<f32>,
+        ix: usize,
+        target_size: Pixels,
+        container_size: Pixels,
+        min_sizes: &[Pixels],
+    ) -> bool {
+        let len = flexes.len();
+        let size = |flex: f32| container_size * (flex / len as f32);
+        let to_flex = |pixels: Pixels| pixels / container_size * len as f32;
+
+        // Don't allow resizing to less than the minimum size, if elements are already too small
+        if min_sizes[ix] - px(1.) > size(flexes[ix]) {
+            return false;
+        }
+
+        let proposed_change = target_size - size(flexes[ix]);
+        let (receiver_ix, donors): (usize, Vec<usize>) = if proposed_change > px(0.) {
+            (ix, (ix + 1..len).collect())
+        } else {
+            (ix + 1, (0..=ix).rev().collect())
+        };
+
+        let mut remaining = proposed_change.abs();
+        let mut deltas = vec![0.; len];
+        for donor_ix in donors {
+            if remaining <= px(0.) {
+                break;
+            }
+            let available = Pixels::max(size(flexes[donor_ix]) - min_sizes[donor_ix], px(0.));
+            let taken = Pixels::min(available, remaining);
+            deltas[donor_ix] -= to_flex(taken);
+            deltas[receiver_ix] += to_flex(taken);
+            remaining -= taken;
+        }
+
+        for (flex, delta) in flexes.iter_mut().zip(deltas) {
+            *flex += delta;
+        }
+
+        true
+    }
+
+    /// The minimum length of each of `len` children along the axis: the largest of the
+    /// axis-wide `min_size`, the child's own entry in `child_min_sizes`, and a small floor
+    /// that applies even when the settings allow zero-sized panes. Children marked in
+    /// `minimized` only get the floor.
+    pub(super) fn child_min_sizes(
+        min_size: Pixels,
+        child_min_sizes: &[Pixels],
+        minimized: &[bool],
+        len: usize,
+    ) -> Vec<Pixels> {
+        (0..len)
+            .map(|ix| {
+                if minimized.get(ix).copied().unwrap_or(false) {
+                    return px(MIN_CHILD_SIZE);
+                }
+                let child_min_size = child_min_sizes.get(ix).copied().unwrap_or_default();
+                min_size.max(child_min_size).max(px(MIN_CHILD_SIZE))
+            })
+            .collect()
+    }
+
+    /// Grows children that `container_size` would make smaller than their entry in
+    /// `min_sizes`, taking the space from the others in proportion to how far above their
+    /// minimum they are. The stored flexes are left alone, so the original sizes come back
+    /// once there's room. When the container can't fit every child at its minimum, they're
+    /// sized evenly.
+    pub(super) fn fit_to_min_size(
+        flexes: &[f32],
+        container_size: Pixels,
+        min_sizes: &[Pixels],
+    ) -> Vec<f32> {
+        let len = flexes.len() as f32;
+        let min_flexes: Vec<f32> = min_sizes
+            .iter()
+            .map(|min_size| *min_size / container_size * len)
+            .collect();
+        let total_min_flex: f32 = min_flexes.iter().sum();
+        if !total_min_flex.is_finite() || total_min_flex >= len {
+            return vec![1.; flexes.len()];
+        }
+
+        let deficit: f32 = flexes
+            .iter()
+            .zip(&min_flexes)
+            .map(|(flex, min_flex)| (min_flex - flex).max(0.))
+            .sum();
+        if deficit == 0. {
+            return flexes.to_vec();
+        }
+        let excess: f32 = flexes
+            .iter()
+            .zip(&min_flexes)
+            .map(|(flex, min_flex)| (flex - min_flex).max(0.))
+            .sum();
+        flexes
+            .iter()
+            .zip(&min_flexes)
+            .map(|(&flex, &min_flex)| {
+                if flex < min_flex {
+                    min_flex
+                } else {
+                    flex - deficit * (flex - min_flex) / excess
+                }
+            })
+            .collect()
+    }
+
+    /// Where children of `child_sizes` start along an axis of `container_size` that's been
+    /// scrolled by `scroll_offset`, along with that offset clamped to how far they overflow.
+    pub(super) fn scrolled_offsets(
+        child_sizes: &[Pixels],
+        container_size: Pixels,
+        gap: Pixels,
+        scroll_offset: Pixels,
+    ) -> (Vec<Pixels>, Pixels) {
+        let content_size = child_sizes
+            .iter()
+            .fold(px(0.), |total, child_size| total + *child_size)
+            + gap * child_sizes.len().saturating_sub(1) as f32;
+        let max_offset = Pixels::max(content_size - container_size, px(0.));
+        let scroll_offset = Pixels::max(px(0.), Pixels::min(scroll_offset, max_offset));
+
+        let mut offset = -scroll_offset;
+        let offsets = child_sizes
+            .iter()
+            .map(|child_size| {
+                let child_offset = offset;
+                offset += *child_size + gap;
+                child_offset
+            })
+            .collect();
+        (offsets, scroll_offset)
+    }
+
+    /// The shares of the whole axis held by the children on either side of handle `ix`,
+    /// given every child's laid out `sizes`, formatted like "60% / 40%".
+    pub(super) fn split_percentages(sizes: &[f32], ix: usize) -> String {
+        let total: f32 = sizes.iter().sum();
+        let percentage = |size: f32| (size / total * 100.).round();
+        format!(
+            "{}% / {}%",
+            percentage(sizes[ix]),
+            percentage(sizes[ix + 1])
+        )
+    }
+
+    /// How far one modifier-scroll over a divider moves it. Scrolling up or left (a positive
+    /// delta) moves the divider towards the start of the axis, whichever component is larger.
+    pub(super) fn scroll_resize_step(delta: Point<Pixels>) -> Pixels {
+        let delta = if delta.x.abs() > delta.y.abs() {
+            delta.x
+        } else {
+            delta.y
+        };
+        if delta > px(0.) {
+            -px(RESIZE_STEP)
+        } else if delta < px(0.) {
+            px(RESIZE_STEP)
+        } else {
+            px(0.)
+        }
+    }
+
+    /// How far an arrow key moves a focused handle along `axis`. Keys across the axis, and
+    /// any other key, don't move it.
+    pub(super) fn keyboard_resize_step(axis: Axis, key: &str) -> Option<Pixels> {
+        match (axis, key) {
+            (Axis::Horizontal, "left") | (Axis::Vertical, "up") => Some(-px(RESIZE_STEP)),
+            (Axis::Horizontal, "right") | (Axis::Vertical, "down") => Some(px(RESIZE_STEP)),
+            _ => None,
+        }
+    }
+
+    /// Whether the mouse has moved far enough from where it went down on a handle to resize.
+    pub(super) fn past_drag_threshold(from: Point<Pixels>, to: Point<Pixels>) -> bool {
+        (to - from).magnitude() >= DRAG_THRESHOLD as f64
+    }
+
+    /// A description of handle `ix` for assistive technology, counting panes from one.
+    pub(super) fn divider_label(ix: usize) -> String {
+        format!("Resize divider between pane {} and {}", ix + 1, ix + 2)
+    }
+
+    /// Rounds `size` to the nearest tenth of `container_size`, for dragging to exact ratios.
+    pub(super) fn snap_to_tenth(size: Pixels, container_size: Pixels) -> Pixels {
+        container_size * ((size / container_size * 10.).round() / 10.)
+    }
+
+    /// The size of one flex unit when `len` children share `container_size`. An unbounded
+    /// container (e.g. inside a scroll view) gives every child a fixed size instead.
+    pub(super) fn space_per_flex(container_size: Pixels, len: usize) -> Pixels {
+        if container_size.0.is_finite() {
+            container_size / len as f32
+        } else {
+            // This runs on every paint, so only the first occurrence is logged
+            static WARN_UNBOUNDED: Once = Once::new();
+            WARN_UNBOUNDED.call_once(|| {
+                log::warn!("pane axis laid out with an unbounded size of {container_size:?}")
+            });
+            px(FALLBACK_CHILD_SIZE)
+        }
+    }
+
+    /// Which of `handles`, given as their nesting depth and bounds, should respond to the
+    /// mouse at `position`: the most deeply nested one containing it, or the last of several
+    /// that are equally deep, as it's painted on top.
+    pub(super) fn hovered_handle(
+        handles: &[(usize, Bounds<Pixels>)],
+        position: Point<Pixels>,
+    ) -> Option<usize> {
+        handles
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, bounds))| bounds.contains(&position))
+            .max_by_key(|(_, (depth, _))| *depth)
+            .map(|(ix, _)| ix)
+    }
+
+    /// The area around the trailing edge of `pane_bounds` that can be dragged to resize it,
+    /// centered on the `gap` that follows the pane.
+    pub(super) fn handle_bounds(
+        axis: Axis,
+        pane_bounds: Bounds<Pixels>,
+        handle_size: Pixels,
+        gap: Pixels,
+    ) -> Bounds<Pixels> {
+        Bounds {
+            origin: pane_bounds.origin.apply_along(axis, |origin| {
+                origin + pane_bounds.size.along(axis) + gap / 2. - handle_size / 2.
+            }),
+            size: pane_bounds.size.apply_along(axis, |_| handle_size),
+        }
+    }
+
+    /// The color of the dividers between the children of an axis.
+    pub(super) fn divider_color(axis: Axis, colors: &ThemeColors) -> Hsla {
+        match axis {
+            Axis::Horizontal => colors.horizontal_pane_divider,
+            Axis::Vertical => colors.vertical_pane_divider,
+        }
+    }
+
+    /// The magnification to apply for the `active_pane_magnification` setting, which is
+    /// kept at or above [`MIN_MAGNIFICATION`] so that the active pane never collapses.
+    fn clamp_magnification(magnification: f32) -> f32 {
+        if magnification.is_finite() {
+            magnification.max(MIN_MAGNIFICATION)
+        } else {
+            1.
+        }
+    }
+
+    /// The flexes used to lay out an axis' children, with the child containing the active
+    /// pane grown by `magnification`. The result always sums to the number of children.
+    pub(super) fn magnified_flexes(
+        flexes: &[f32],
+        active_pane_ix: Option<usize>,
+        magnification: f32,
+    ) -> Vec<f32> {
+        let magnification = clamp_magnification(magnification);
+        let mut flexes = flexes.to_vec();
+        if let Some(ix) = active_pane_ix.filter(|_| magnification != 1.) {
+            flexes[ix] *= magnification;
+            normalize_flexes(&mut flexes);
+        }
+        flexes
+    }
+
+    /// The inverse of [`magnified_flexes`], turning layout flexes back into stored ones.
+    pub(super) fn unmagnified_flexes(
+        layout_flexes: &[f32],
+        active_pane_ix: Option<usize>,
+        magnification: f32,
+    ) -> Vec<f32> {
+        let magnification = clamp_magnification(magnification);
+        let mut flexes = layout_flexes.to_vec();
+        if let Some(ix) = active_pane_ix.filter(|_| magnification != 1.) {
+            flexes[ix] /= magnification;
+            normalize_flexes(&mut flexes);
+        }
+        flexes
+    }
+
+    /// Rescales `flexes` to sum to their count, first replacing negative, zero or non-finite
+    /// values with a small positive flex so that no member disappears.
+    pub(super) fn normalize_flexes(flexes: &mut [f32]) {
+        for flex in flexes.iter_mut() {
+            if !flex.is_finite() || *flex < MIN_FLEX {
+                *flex = MIN_FLEX;
+            }
+        }
+        let scale = flexes.len() as f32 / flexes.iter().sum::<f32>();
+        for flex in flexes.iter_mut() {
+            *flex *= scale;
+        }
+    }
+
+    /// Sets child `ix` to `flex`, scaling the others so that they keep their ratios and the
+    /// flexes still sum to their count.
+    pub(super) fn set_flex(flexes: &mut [f32], ix: usize, flex: f32) {
+        let len = flexes.len() as f32;
+        let flex = flex.min(len - MIN_FLEX * (len - 1.));
+        let others: f32 = flexes.iter().sum::<f32>() - flexes[ix];
+        let scale = (len - flex) / others;
+        for (other_ix, other) in flexes.iter_mut().enumerate() {
+            if other_ix != ix {
+                *other *= scale;
+            }
+        }
+        flexes[ix] = flex;
+        normalize_flexes(flexes);
+    }
+
+    /// Splits the space of the two panes on either side of handle `ix` evenly between them.
+    pub(super) fn equalize_adjacent(flexes: &mut [f32], ix: usize) {
+        let average = (flexes[ix] + flexes[ix + 1]) / 2.;
+        flexes[ix] = average;
+        flexes[ix + 1] = average;
+    }
+
+    fn flex_values_in_bounds(flexes: &[f32]) -> bool {
+        (flexes.iter().copied().sum::<f32>() - flexes.len() as f32).abs() < 0.001
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{persistence::model::SerializedItem, ScrollResizeModifier};
+    use gpui::{
+        Modifiers, MouseDownEvent, MouseMoveEvent, MouseUpEvent, TestAppContext, VisualTestContext,
+    };
+    use project::FakeFs;
+    use settings::SettingsStore;
+    use std::{cell::RefCell, rc::Rc};
+    use theme::{LoadThemes, StatusBoxCorner, ThemeColors};
+
+    #[gpui::test]
+    async fn test_swap(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c] = add_panes(&workspace, cx);
+
+        // -------------
+        // | a | b     |
+        // |   | - - - |
+        // |   | c     |
+        // -------------
+        let mut group = PaneGroup::new(a.clone());
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+        group.split(&b, &c, SplitDirection::Down).unwrap();
+        set_flexes(&group.root, &[], vec![1.5, 0.5]);
+        set_flexes(&group.root, &[1], vec![0.8, 1.2]);
+
+        group.swap(&a, &c).unwrap();
+        assert_eq!(layout(&group, &[&a, &b, &c]), "h[2,v[1,0]]");
+        assert_eq!(flexes_at(&group.root, &[]), vec![1.5, 0.5]);
+        assert_eq!(flexes_at(&group.root, &[1]), vec![0.8, 1.2]);
+
+        let [d] = add_panes(&workspace, cx);
+        assert!(group.swap(&a, &d).is_err());
+        assert_eq!(layout(&group, &[&a, &b, &c]), "h[2,v[1,0]]");
+    }
+
+    #[gpui::test]
+    async fn test_max_panes(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+
+        let mut group = PaneGroup::new(a.clone());
+        group.set_max_panes(Some(2));
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+
+        let error = group.split(&b, &c, SplitDirection::Down).unwrap_err();
+        assert_eq!(error.to_string(), "Maximum pane count reached");
+        assert!(group
+            .split_grid(&a, vec![c.clone(), d.clone()], 1, 3)
+            .is_err());
+        assert_eq!(layout(&group, &[&a, &b, &c]), "h[0,1]");
+
+        group.set_max_panes(None);
+        group.split(&b, &c, SplitDirection::Down).unwrap();
+        assert_eq!(layout(&group, &[&a, &b, &c]), "h[0,v[1,2]]");
+    }
+
+    #[gpui::test]
+    async fn test_split_rejects_present_pane(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c] = add_panes(&workspace, cx);
+
+        let mut group = PaneGroup::new(a.clone());
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+
+        let error = group.split(&a, &b, SplitDirection::Down).unwrap_err();
+        assert_eq!(error.to_string(), "Pane already present");
+        let error = group.split(&b, &b, SplitDirection::Down).unwrap_err();
+        assert_eq!(error.to_string(), "Pane already present");
+        assert!(group
+            .split_with_ratio(&b, &a, SplitDirection::Left, 0.5)
+            .is_err());
+        assert_eq!(layout(&group, &[&a, &b, &c]), "h[0,1]");
+
+        group.split(&b, &c, SplitDirection::Down).unwrap();
+        assert_eq!(layout(&group, &[&a, &b, &c]), "h[0,v[1,2]]");
+    }
+
+    #[gpui::test]
+    async fn test_replace_pane(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+
+        let mut group = PaneGroup::new(a.clone());
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+        group.split(&b, &c, SplitDirection::Down).unwrap();
+        element::resize_flexes(
+            &mut axis_at(&group.root, &[1]).flexes.lock(),
+            0,
+            px(300.),
+            px(400.),
+            &[px(20.); 2],
+        );
+        let flexes = flexes_at(&group.root, &[1]);
+        group.minimize(&c).unwrap();
+        group.set_locked(&c, true).unwrap();
+        let minimized_flexes = flexes_at(&group.root, &[1]);
+
+        group.replace_pane(&c, d.clone()).unwrap();
+        assert_eq!(layout(&group, &[&a, &b, &c, &d]), "h[0,v[1,3]]");
+        assert_eq!(flexes_at(&group.root, &[1]), minimized_flexes);
+
+        // The replacement takes over the minimized and locked state.
+        assert!(!group.is_minimized(&c));
+        assert!(!group.is_locked(&c));
+        assert!(group.is_minimized(&d));
+        assert!(group.is_locked(&d));
+        group.restore(&d).unwrap();
+        assert_flexes(flexes_at(&group.root, &[1]), &flexes);
+
+        assert!(group.replace_pane(&c, d.clone()).is_err());
+    }
+
+    #[gpui::test]
+    async fn test_split_returning_axis(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d, e] = add_panes(&workspace, cx);
+
+        let mut group = PaneGroup::new(a.clone());
+        assert_eq!(
+            group
+                .split_returning_axis(&a, &b, SplitDirection::Right)
+                .unwrap(),
+            SplitOutcome::CreatedAxis
+        );
+        assert_eq!(
+            group
+                .split_returning_axis(&a, &c, SplitDirection::Right)
+                .unwrap(),
+            SplitOutcome::ExtendedAxis { index: 1 }
+        );
+        assert_eq!(
+            group
+                .split_returning_axis(&a, &d, SplitDirection::Left)
+                .unwrap(),
+            SplitOutcome::ExtendedAxis { index: 0 }
+        );
+        assert_eq!(
+            group
+                .split_returning_axis(&b, &e, SplitDirection::Down)
+                .unwrap(),
+            SplitOutcome::CreatedAxis
+        );
+        assert_eq!(layout(&group, &[&a, &b, &c, &d, &e]), "h[3,0,2,v[1,4]]");
+    }
+
+    #[gpui::test]
+    async fn test_set_flexes(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c] = add_panes(&workspace, cx);
+
+        let axis = PaneAxis::new(
+            Axis::Horizontal,
+            vec![
+                Member::Pane(a.clone()),
+                Member::Pane(b.clone()),
+                Member::Pane(c.clone()),
+            ],
+        );
+        assert_eq!(axis.flexes(), [1., 1., 1.]);
+
+        let error = axis.set_flexes(vec![1., 2.]).unwrap_err();
+        assert_eq!(error.to_string(), "Expected 3 flexes but got 2");
+        assert_eq!(axis.flexes(), [1., 1., 1.]);
+
+        axis.set_flexes(vec![0.5, 1.5, 1.]).unwrap();
+        assert_eq!(axis.flexes(), [0.5, 1.5, 1.]);
+        axis.set_flexes(vec![1., 2., 3.]).unwrap();
+        assert_flexes(axis.flexes(), &[0.5, 1., 1.5]);
+    }
+
+    #[gpui::test]
+    async fn test_split_with_ratio(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+
+        let mut group = PaneGroup::new(a.clone());
+        group
+            .split_with_ratio(&a, &b, SplitDirection::Right, 0.3)
+            .unwrap();
+        assert_eq!(layout(&group, &[&a, &b, &c, &d]), "h[0,1]");
+        assert_flexes(flexes_at(&group.root, &[]), &[1.4, 0.6]);
+
+        // Splitting along the same axis only rescales the pane being split.
+        group
+            .split_with_ratio(&b, &c, SplitDirection::Left, 0.5)
+            .unwrap();
+        assert_eq!(layout(&group, &[&a, &b, &c, &d]), "h[0,2,1]");
+        assert_flexes(flexes_at(&group.root, &[]), &[1.4, 0.3, 0.3]);
+
+        // Splitting across the axis wraps the pane in a new axis, and the ratio is clamped.
+        group
+            .split_with_ratio(&a, &d, SplitDirection::Down, 0.99)
+            .unwrap();
+        assert_eq!(layout(&group, &[&a, &b, &c, &d]), "h[v[0,3],2,1]");
+        assert_flexes(flexes_at(&group.root, &[]), &[1.4, 0.3, 0.3]);
+        assert_flexes(flexes_at(&group.root, &[0]), &[0.2, 1.8]);
+    }
+
+    #[gpui::test]
+    async fn test_adjacent_pane(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+
+        // ---------
+        // | a | b |
+        // | - | - |
+        // | c | d |
+        // ---------
+        let group = grid(&a, &b, &c, &d);
+
+        assert_eq!(
+            group.adjacent_pane(&a, SplitDirection::Right),
+            Some(b.clone())
+        );
+        assert_eq!(
+            group.adjacent_pane(&a, SplitDirection::Down),
+            Some(c.clone())
+        );
+        assert_eq!(group.adjacent_pane(&a, SplitDirection::Left), None);
+        assert_eq!(group.adjacent_pane(&a, SplitDirection::Up), None);
+        assert_eq!(group.adjacent_pane(&d, SplitDirection::Up), Some(b.clone()));
+        assert_eq!(
+            group.adjacent_pane(&d, SplitDirection::Left),
+            Some(a.clone())
+        );
+        assert_eq!(group.adjacent_pane(&d, SplitDirection::Right), None);
+    }
+
+    #[gpui::test]
+    async fn test_neighbors(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d, e] = add_panes(&workspace, cx);
+
+        // Up, down, left, right
+        let group = grid(&a, &b, &c, &d);
+        assert_eq!(
+            group.neighbors(&a),
+            [None, Some(c.clone()), None, Some(b.clone())]
+        );
+        assert_eq!(
+            group.neighbors(&d),
+            [Some(b.clone()), None, Some(a.clone()), None]
+        );
+        assert_eq!(group.neighbors(&e), [None, None, None, None]);
+
+        let mut group = PaneGroup::new(a.clone());
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+        group.split(&b, &c, SplitDirection::Right).unwrap();
+        assert_eq!(
+            group.neighbors(&b),
+            [None, None, Some(a.clone()), Some(c.clone())]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_equalize(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+
+        let mut group = PaneGroup::new(a.clone());
+        group.equalize();
+        assert_eq!(layout(&group, &[&a]), "0");
+
+        let mut group = grid(&a, &b, &c, &d);
+        set_flexes(&group.root, &[], vec![1.6, 0.4]);
+        set_flexes(&group.root, &[0], vec![0.3, 1.7]);
+        set_flexes(&group.root, &[1], vec![1.1, 0.9]);
+
+        group.equalize();
+        assert_eq!(flexes_at(&group.root, &[]), vec![1., 1.]);
+        assert_eq!(flexes_at(&group.root, &[0]), vec![1., 1.]);
+        assert_eq!(flexes_at(&group.root, &[1]), vec![1., 1.]);
+    }
+
+    #[gpui::test]
+    async fn test_rotate_containing(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+
+        let mut group = PaneGroup::new(a.clone());
+        group.rotate_containing(&a).unwrap();
+        assert_eq!(layout(&group, &[&a]), "0");
+
+        group.split(&a, &b, SplitDirection::Down).unwrap();
+        group.split(&b, &c, SplitDirection::Right).unwrap();
+        set_flexes(&group.root, &[], vec![1.2, 0.8]);
+        set_flexes(&group.root, &[1], vec![0.5, 1.5]);
+
+        group.rotate_containing(&c).unwrap();
+        assert_eq!(layout(&group, &[&a, &b, &c]), "v[0,v[1,2]]");
+        assert_eq!(flexes_at(&group.root, &[1]), vec![0.5, 1.5]);
+
+        group.rotate_containing(&a).unwrap();
+        assert_eq!(layout(&group, &[&a, &b, &c]), "h[0,v[1,2]]");
+        assert_eq!(flexes_at(&group.root, &[]), vec![1.2, 0.8]);
+
+        assert!(group.rotate_containing(&d).is_err());
+    }
+
+    #[gpui::test]
+    async fn test_remove_returning_neighbor(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+
+        let mut group = PaneGroup::new(a.clone());
+        assert_eq!(group.remove_returning_neighbor(&a).unwrap(), None);
+
+        // Removing a middle pane hands focus to its previous sibling.
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+        group.split(&b, &c, SplitDirection::Right).unwrap();
+        assert_eq!(layout(&group, &[&a, &b, &c]), "h[0,1,2]");
+        assert_eq!(
+            group.remove_returning_neighbor(&b).unwrap(),
+            Some(a.clone())
+        );
+        assert_eq!(layout(&group, &[&a, &b, &c]), "h[0,2]");
+
+        // The first pane has no previous sibling, so the next one is used, reaching into
+        // the nearest pane of a nested axis.
+        group.split(&c, &d, SplitDirection::Down).unwrap();
+        assert_eq!(layout(&group, &[&a, &b, &c, &d]), "h[0,v[2,3]]");
+        assert_eq!(
+            group.remove_returning_neighbor(&a).unwrap(),
+            Some(c.clone())
+        );
+        assert_eq!(layout(&group, &[&a, &b, &c, &d]), "v[2,3]");
+
+        // Removing one of two panes collapses the axis onto the survivor.
+        assert_eq!(
+            group.remove_returning_neighbor(&d).unwrap(),
+            Some(c.clone())
+        );
+        assert_eq!(layout(&group, &[&a, &b, &c, &d]), "2");
+
+        group.split(&c, &a, SplitDirection::Up).unwrap();
+        assert!(group.remove_returning_neighbor(&b).is_err());
+    }
+
+    #[gpui::test]
+    async fn test_locked_panes(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c] = add_panes(&workspace, cx);
+
+        let mut group = PaneGroup::new(a.clone());
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+        assert!(group.set_locked(&c, true).is_err());
+
+        group.set_locked(&b, true).unwrap();
+        assert!(group.is_locked(&b));
+        assert!(!group.remove(&b).unwrap());
+        assert_eq!(group.remove_returning_neighbor(&b).unwrap(), None);
+        assert_eq!(layout(&group, &[&a, &b]), "h[0,1]");
+
+        // Locks follow their pane through a remap.
+        group.map_panes(|pane| if pane == &b { c.clone() } else { pane.clone() });
+        assert!(group.is_locked(&c));
+        assert!(!group.is_locked(&b));
+
+        group.set_locked(&c, false).unwrap();
+        assert!(group.remove(&c).unwrap());
+        assert_eq!(layout(&group, &[&a]), "0");
+    }
+
+    #[gpui::test]
+    async fn test_remove_all_except(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d, e] = add_panes(&workspace, cx);
+
+        let mut group = grid(&a, &b, &c, &d);
+        assert!(group.remove_all_except(&e).is_err());
+
+        group.set_locked(&d, true).unwrap();
+        assert!(group.remove_all_except(&b).is_err());
+        assert_eq!(layout(&group, &[&a, &b, &c, &d]), "h[v[0,2],v[1,3]]");
+
+        group.set_locked(&d, false).unwrap();
+        group.set_locked(&b, true).unwrap();
+        assert_eq!(group.remove_all_except(&b).unwrap(), vec![a, c, d]);
+        assert!(matches!(&group.root, Member::Pane(pane) if pane == &b));
+        assert!(group.is_locked(&b));
+
+        assert_eq!(group.remove_all_except(&b).unwrap(), vec![]);
+    }
+
+    #[gpui::test]
+    async fn test_find_axis_containing(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d, e] = add_panes(&workspace, cx);
+
+        let mut group = PaneGroup::new(a.clone());
+        assert_eq!(group.find_axis_containing(&a), None);
+
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+        group.split(&b, &c, SplitDirection::Right).unwrap();
+        group.split(&c, &d, SplitDirection::Down).unwrap();
+        assert_eq!(layout(&group, &[&a, &b, &c, &d]), "h[0,1,v[2,3]]");
+
+        assert_eq!(
+            group.find_axis_containing(&a),
+            Some((Axis::Horizontal, 0, 3))
+        );
+        assert_eq!(
+            group.find_axis_containing(&b),
+            Some((Axis::Horizontal, 1, 3))
+        );
+        assert_eq!(group.find_axis_containing(&c), Some((Axis::Vertical, 0, 2)));
+        assert_eq!(group.find_axis_containing(&d), Some((Axis::Vertical, 1, 2)));
+        assert_eq!(group.find_axis_containing(&e), None);
+    }
+
+    #[gpui::test]
+    async fn test_move_pane(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+
+        let mut group = PaneGroup::new(a.clone());
+        assert!(!group.move_pane(&a, SplitDirection::Right).unwrap());
+
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+        group.split(&b, &c, SplitDirection::Right).unwrap();
+        set_flexes(&group.root, &[], vec![0.5, 1., 1.5]);
+
+        assert!(group.move_pane(&a, SplitDirection::Right).unwrap());
+        assert_eq!(layout(&group, &[&a, &b, &c]), "h[1,0,2]");
+        assert_eq!(flexes_at(&group.root, &[]), vec![1., 0.5, 1.5]);
+
+        assert!(group.move_pane(&a, SplitDirection::Right).unwrap());
+        assert_eq!(layout(&group, &[&a, &b, &c]), "h[1,2,0]");
+        assert_eq!(flexes_at(&group.root, &[]), vec![1., 1.5, 0.5]);
+
+        // Already at the edge, or moving across the axis.
+        assert!(!group.move_pane(&a, SplitDirection::Right).unwrap());
+        assert!(!group.move_pane(&a, SplitDirection::Up).unwrap());
+        assert_eq!(layout(&group, &[&a, &b, &c]), "h[1,2,0]");
+
+        assert!(group
+            .move_pane(&b, SplitDirection::Left)
+            .is_ok_and(|moved| !moved));
+        assert!(group.move_pane(&d, SplitDirection::Left).is_err());
+    }
+
+    #[gpui::test]
+    async fn test_pane_bounds(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        cx.simulate_resize(size(px(800.), px(600.)));
+
+        let left = workspace.update(cx, |workspace, cx| {
+            let left = workspace.active_pane().clone();
+            workspace.split_pane(left.clone(), SplitDirection::Right, cx);
+            left
+        });
+        cx.update(|cx| cx.refresh());
+
+        let pane_bounds = workspace.update(cx, |workspace, _| workspace.pane_bounds());
+        assert_eq!(pane_bounds.len(), 2);
+        let (first, first_bounds) = &pane_bounds[0];
+        let (_, second_bounds) = &pane_bounds[1];
+        assert_eq!(first, &left);
+        assert_eq!(first_bounds.size.height, second_bounds.size.height);
+        assert_eq!(first_bounds.upper_right().x, second_bounds.origin.x);
+        assert!(!first_bounds.intersects(second_bounds));
+    }
+
+    #[gpui::test]
+    async fn test_debug_json(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        cx.simulate_resize(size(px(800.), px(600.)));
+
+        let (left, right) = workspace.update(cx, |workspace, cx| {
+            let left = workspace.active_pane().clone();
+            let right = workspace.split_pane(left.clone(), SplitDirection::Right, cx);
+            (left, right)
+        });
+        workspace.update(cx, |workspace, _| {
+            set_flexes(&workspace.center.root, &[], vec![1.5, 0.5])
+        });
+        cx.update(|cx| cx.refresh());
+        let (json, width) = workspace.update(cx, |workspace, _| {
+            let bounds = workspace.center.pane_bounds();
+            let width = bounds[1].1.upper_right().x - bounds[0].1.origin.x;
+            (workspace.center.debug_json(), width.0)
+        });
+        assert_eq!(json["axis"], "horizontal");
+        assert_eq!(json["flexes"], serde_json::json!([1.5, 0.5]));
+        let sizes = json["sizes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|size| size.as_f64().unwrap() as f32)
+            .collect::<Vec<_>>();
+        assert_flexes(sizes, &[width * 0.75, width * 0.25]);
+        assert_eq!(
+            json["children"],
+            serde_json::json!([
+                { "pane": left.entity_id().as_u64() },
+                { "pane": right.entity_id().as_u64() },
+            ])
+        );
+    }
+
+    #[gpui::test]
+    async fn test_pane_gap(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        cx.simulate_resize(size(px(800.), px(600.)));
+
+        workspace.update(cx, |workspace, cx| {
+            let left = workspace.active_pane().clone();
+            workspace.split_pane(left.clone(), SplitDirection::Right, cx);
+        });
+        cx.update(|cx| cx.refresh());
+        let pane_bounds = workspace.update(cx, |workspace, _| workspace.pane_bounds());
+        let total_width = pane_bounds[1].1.upper_right().x - pane_bounds[0].1.origin.x;
+
+        cx.update(|cx| {
+            cx.update_global(|settings: &mut SettingsStore, cx| {
+                settings.update_user_settings::<WorkspaceSettings>(cx, |settings| {
+                    settings.pane_gap = Some(12.);
+                })
+            })
+        });
+        cx.update(|cx| cx.refresh());
+
+        let pane_bounds = workspace.update(cx, |workspace, _| workspace.pane_bounds());
+        let (_, first_bounds) = &pane_bounds[0];
+        let (_, second_bounds) = &pane_bounds[1];
+        assert_eq!(
+            second_bounds.origin.x - first_bounds.upper_right().x,
+            px(12.)
+        );
+        assert_eq!(first_bounds.size.width, second_bounds.size.width);
+        assert_eq!(
+            first_bounds.size.width + second_bounds.size.width + px(12.),
+            total_width
+        );
+        assert_eq!(
+            second_bounds.upper_right().x - first_bounds.origin.x,
+            total_width
+        );
+    }
+
+    #[gpui::test]
+    async fn test_pane_at_pixel_position(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        cx.simulate_resize(size(px(800.), px(600.)));
+
+        let (left, right) = workspace.update(cx, |workspace, cx| {
+            let left = workspace.active_pane().clone();
+            let right = workspace.split_pane(left.clone(), SplitDirection::Right, cx);
+            (left, right)
+        });
+        cx.update(|cx| cx.refresh());
+
+        workspace.update(cx, |workspace, _| {
+            let left_bounds = workspace.center.bounding_box_for_pane(&left).unwrap();
+            let right_bounds = workspace.center.bounding_box_for_pane(&right).unwrap();
+            let pane_at = |x, y| workspace.center.pane_at_pixel_position(point(x, y));
+
+            let y = left_bounds.center().y;
+            assert_eq!(pane_at(left_bounds.center().x, y), Some(&left));
+            assert_eq!(pane_at(right_bounds.center().x, y), Some(&right));
+            assert_eq!(pane_at(left_bounds.upper_right().x, y), Some(&left));
+            assert_eq!(pane_at(right_bounds.upper_right().x + px(1.), y), None);
+            assert_eq!(
+                pane_at(left_bounds.center().x, left_bounds.origin.y - px(1.)),
+                None
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_drag_threshold(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        cx.simulate_resize(size(px(800.), px(600.)));
+
+        let left = workspace.update(cx, |workspace, cx| {
+            let left = workspace.active_pane().clone();
+            workspace.split_pane(left.clone(), SplitDirection::Right, cx);
+            left
+        });
+        cx.update(|cx| cx.refresh());
+        let resizes = Rc::new(RefCell::new(0));
+        cx.update({
+            let resizes = resizes.clone();
+            |cx| {
+                cx.subscribe(&workspace, move |_, event: &PaneGroupEvent, _| {
+                    if *event == PaneGroupEvent::Resized {
+                        *resizes.borrow_mut() += 1;
+                    }
+                })
+                .detach()
+            }
+        });
+
+        let drag_handle = |distance: f32, cx: &mut VisualTestContext| {
+            let left_bounds = workspace.update(cx, |workspace, _| {
+                workspace.center.bounding_box_for_pane(&left).unwrap()
+            });
+            let position = point(left_bounds.upper_right().x, left_bounds.center().y);
+            cx.simulate_event(MouseDownEvent {
+                button: MouseButton::Left,
+                position,
+                ..Default::default()
+            });
+            cx.simulate_event(MouseMoveEvent {
+                position: point(position.x + px(distance), position.y),
+                pressed_button: Some(MouseButton::Left),
+                ..Default::default()
+            });
+            cx.simulate_event(MouseUpEvent {
+                button: MouseButton::Left,
+                position: point(position.x + px(distance), position.y),
+                ..Default::default()
+            });
+            cx.update(|cx| cx.refresh());
+            workspace.update(cx, |workspace, _| flexes_at(&workspace.center.root, &[]))
+        };
+
+        assert_flexes(drag_handle(2., cx), &[1., 1.]);
+        assert_eq!(*resizes.borrow(), 0);
+        let flexes = drag_handle(50., cx);
+        assert!(flexes[0] > 1., "{flexes:?}");
+        assert_eq!(*resizes.borrow(), 1);
+    }
+
+    #[test]
+    fn test_past_drag_threshold() {
+        let from = point(px(100.), px(100.));
+        assert!(!element::past_drag_threshold(from, from));
+        assert!(!element::past_drag_threshold(
+            from,
+            point(px(102.), px(102.))
+        ));
+        assert!(element::past_drag_threshold(
+            from,
+            point(px(103.), px(100.))
+        ));
+        assert!(element::past_drag_threshold(from, point(px(100.), px(97.))));
+    }
+
+    #[gpui::test]
+    async fn test_locked_layout(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        cx.simulate_resize(size(px(800.), px(600.)));
+
+        let left = workspace.update(cx, |workspace, cx| {
+            let left = workspace.active_pane().clone();
+            workspace.split_pane(left.clone(), SplitDirection::Right, cx);
+            left
+        });
+        cx.update(|cx| cx.refresh());
+
+        let drag_handle = |cx: &mut VisualTestContext| {
+            let left_bounds = workspace.update(cx, |workspace, _| {
+                workspace.center.bounding_box_for_pane(&left).unwrap()
+            });
+            let position = point(left_bounds.upper_right().x, left_bounds.center().y);
+            cx.simulate_event(MouseDownEvent {
+                button: MouseButton::Left,
+                position,
+                ..Default::default()
+            });
+            cx.simulate_event(MouseMoveEvent {
+                position: point(position.x + px(100.), position.y),
+                pressed_button: Some(MouseButton::Left),
+                ..Default::default()
+            });
+            cx.simulate_event(MouseUpEvent {
+                button: MouseButton::Left,
+                position: point(position.x + px(100.), position.y),
+                ..Default::default()
+            });
+            workspace.update(cx, |workspace, _| flexes_at(&workspace.center.root, &[]))
+        };
+
+        cx.update(|cx| {
+            cx.update_global(|settings: &mut SettingsStore, cx| {
+                settings.update_user_settings::<WorkspaceSettings>(cx, |settings| {
+                    settings.resizable_panes = Some(false);
+                })
+            })
+        });
+        cx.update(|cx| cx.refresh());
+        assert_flexes(drag_handle(cx), &[1., 1.]);
+
+        cx.update(|cx| {
+            cx.update_global(|settings: &mut SettingsStore, cx| {
+                settings.update_user_settings::<WorkspaceSettings>(cx, |settings| {
+                    settings.resizable_panes = Some(true);
+                })
+            })
+        });
+        cx.update(|cx| cx.refresh());
+        let flexes = drag_handle(cx);
+        assert!(flexes[0] > 1., "{flexes:?}");
+    }
+
+    #[gpui::test]
+    async fn test_handle_context_menu(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        cx.simulate_resize(size(px(800.), px(600.)));
+        let (top_left, top_right) = workspace.update(cx, |workspace, cx| {
+            let top_left = workspace.active_pane().clone();
+            let top_right = workspace.split_pane(top_left.clone(), SplitDirection::Right, cx);
+            workspace.split_pane(top_left.clone(), SplitDirection::Down, cx);
+            workspace.split_pane(top_right.clone(), SplitDirection::Down, cx);
+            (top_left, top_right)
+        });
+        cx.update(|cx| cx.refresh());
+
+        let (top_left_bounds, top_right_bounds) = workspace.update(cx, |workspace, _| {
+            (
+                workspace.center.bounding_box_for_pane(&top_left).unwrap(),
+                workspace.center.bounding_box_for_pane(&top_right).unwrap(),
+            )
+        });
+        let handles: [(&[usize], Point<Pixels>); 3] = [
+            (
+                &[],
+                point(top_left_bounds.upper_right().x, top_left_bounds.center().y),
+            ),
+            (
+                &[0],
+                point(top_left_bounds.center().x, top_left_bounds.lower_left().y),
+            ),
+            (
+                &[1],
+                point(top_right_bounds.center().x, top_right_bounds.lower_left().y),
+            ),
+        ];
+        for (path, position) in handles {
+            cx.simulate_event(MouseDownEvent {
+                button: MouseButton::Right,
+                position,
+                ..Default::default()
+            });
+            workspace.update(cx, |workspace, cx| {
+                let handle_menu = workspace.pane_handle_menu.take().unwrap();
+                assert!(Arc::ptr_eq(
+                    &handle_menu.flexes,
+                    &axis_at(&workspace.center.root, path).flexes
+                ));
+                assert_eq!(handle_menu.ix, 0);
+                assert!(!workspace.is_resizing_panes());
+                cx.notify();
+            });
+            cx.update(|cx| cx.refresh());
+        }
+
+        let [root_flexes, column_flexes] = workspace.update(cx, |workspace, _| {
+            set_flexes(&workspace.center.root, &[], vec![1.5, 0.5]);
+            set_flexes(&workspace.center.root, &[1], vec![0.5, 1.5]);
+            let paths: [&[usize]; 2] = [&[], &[1]];
+            paths.map(|path| axis_at(&workspace.center.root, path).flexes.clone())
+        });
+        let events = Rc::new(RefCell::new(Vec::new()));
+        cx.update({
+            let events = events.clone();
+            |cx| {
+                cx.subscribe(&workspace, move |_, event: &PaneGroupEvent, _| {
+                    events.borrow_mut().push(*event)
+                })
+                .detach()
+            }
+        });
+        workspace.update(cx, |workspace, cx| {
+            workspace.apply_pane_handle_action(&column_flexes, 0, HandleAction::Reset, cx);
+            assert_flexes(flexes_at(&workspace.center.root, &[1]), &[1., 1.]);
+            assert_flexes(flexes_at(&workspace.center.root, &[]), &[1.5, 0.5]);
+
+            // A column turned into a row joins the row it's in, keeping its share of the space
+            workspace.apply_pane_handle_action(&column_flexes, 0, HandleAction::RotateAxis, cx);
+            let root = axis_at(&workspace.center.root, &[]);
+            assert_eq!(root.axis, Axis::Horizontal);
+            assert_eq!(root.members.len(), 3);
+            assert_flexes(
+                flexes_at(&workspace.center.root, &[]),
+                &[2.25, 0.375, 0.375],
+            );
+
+            // The column is gone, so there's nothing left for its handle to act on
+            workspace.apply_pane_handle_action(&column_flexes, 0, HandleAction::Reset, cx);
+
+            workspace.apply_pane_handle_action(&root_flexes, 0, HandleAction::Equalize, cx);
+            assert_flexes(flexes_at(&workspace.center.root, &[]), &[1., 1., 1.]);
+        });
+        assert_eq!(
+            events.borrow_mut().drain(..).collect::<Vec<_>>(),
+            [
+                PaneGroupEvent::Resized,
+                PaneGroupEvent::Rotated,
+                PaneGroupEvent::Resized
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_resizing_flag(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        cx.simulate_resize(size(px(800.), px(600.)));
+        let left = workspace.update(cx, |workspace, cx| {
+            let left = workspace.active_pane().clone();
+            workspace.split_pane(left.clone(), SplitDirection::Right, cx);
+            left
+        });
+        cx.update(|cx| cx.refresh());
+        assert!(!workspace.update(cx, |workspace, _| workspace.is_resizing_panes()));
+
+        let left_bounds = workspace.update(cx, |workspace, _| {
+            workspace.center.bounding_box_for_pane(&left).unwrap()
+        });
+        let position = point(left_bounds.upper_right().x, left_bounds.center().y);
+        cx.simulate_event(MouseDownEvent {
+            button: MouseButton::Left,
+            position,
+            ..Default::default()
+        });
+        assert!(workspace.update(cx, |workspace, _| workspace.is_resizing_panes()));
+        cx.simulate_event(MouseMoveEvent {
+            position: point(position.x - px(50.), position.y),
+            pressed_button: Some(MouseButton::Left),
+            ..Default::default()
+        });
+        assert!(workspace.update(cx, |workspace, _| workspace.is_resizing_panes()));
+        cx.simulate_event(MouseUpEvent {
+            button: MouseButton::Left,
+            position: point(position.x - px(50.), position.y),
+            ..Default::default()
+        });
+        assert!(!workspace.update(cx, |workspace, _| workspace.is_resizing_panes()));
+    }
+
+    #[gpui::test]
+    async fn test_can_split(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c] = add_panes(&workspace, cx);
+        let (min_width, min_height) = cx.update(|cx| {
+            let settings = WorkspaceSettings::get_global(cx);
+            (settings.pane_min_width, settings.pane_min_height)
+        });
+
+        let mut group = PaneGroup::new(a.clone());
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+        let can_split = |group: &PaneGroup, available, direction, cx: &mut VisualTestContext| {
+            cx.update(|cx| axis_at(&group.root, &[]).can_split(available, direction, cx))
+        };
+
+        // Along the axis, all three panes need their minimum width.
+        assert!(can_split(&group, 3. * min_width, SplitDirection::Right, cx));
+        assert!(!can_split(
+            &group,
+            3. * min_width - 0.1,
+            SplitDirection::Left,
+            cx
+        ));
+        // Across it, a pane only shares the space with the one it's split from.
+        assert!(can_split(&group, 2. * min_height, SplitDirection::Down, cx));
+        assert!(!can_split(
+            &group,
+            2. * min_height - 0.1,
+            SplitDirection::Up,
+            cx
+        ));
+
+        // A pane's own minimum counts, including from inside a nested axis.
+        b.update(cx, |b, cx| b.set_min_size(size(px(300.), px(0.)), cx));
+        group.split(&b, &c, SplitDirection::Down).unwrap();
+        assert!(can_split(
+            &group,
+            2. * min_width + 300.,
+            SplitDirection::Right,
+            cx
+        ));
+        assert!(!can_split(
+            &group,
+            2. * min_width + 299.9,
+            SplitDirection::Right,
+            cx
+        ));
+        assert!(can_split(&group, 2. * min_height, SplitDirection::Down, cx));
+        assert!(!can_split(
+            &group,
+            2. * min_height - 0.1,
+            SplitDirection::Down,
+            cx
+        ));
+    }
+
+    #[gpui::test]
+    async fn test_group_can_split(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b] = add_panes(&workspace, cx);
+        let (min_width, min_height) = cx.update(|cx| {
+            let settings = WorkspaceSettings::get_global(cx);
+            (px(settings.pane_min_width), px(settings.pane_min_height))
+        });
+
+        // A lone pane hasn't been laid out by an axis, so nothing stops it from splitting.
+        let mut group = PaneGroup::new(a.clone());
+        assert!(cx.update(|cx| group.can_split(&a, SplitDirection::Right, cx)));
+
+        // Until the axis is painted, there's no space to go by.
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+        assert!(cx.update(|cx| group.can_split(&a, SplitDirection::Right, cx)));
+
+        // Two panes painted at their minimum width leave no room for a third beside them,
+        // but there's room above or below either of them.
+        *axis_at(&group.root, &[]).bounding_boxes.lock() = vec![
+            Some(Bounds::new(
+                point(px(0.), px(0.)),
+                size(min_width, min_height * 2.),
+            )),
+            Some(Bounds::new(
+                point(min_width, px(0.)),
+                size(min_width, min_height * 2.),
+            )),
+        ];
+        cx.update(|cx| {
+            assert!(!group.can_split(&a, SplitDirection::Right, cx));
+            assert!(!group.can_split(&b, SplitDirection::Left, cx));
+            assert!(group.can_split(&a, SplitDirection::Down, cx));
+            assert!(group.can_split(&b, SplitDirection::Up, cx));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_pane_min_size_limits_drag(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        cx.simulate_resize(size(px(800.), px(600.)));
+
+        let left = workspace.update(cx, |workspace, cx| {
+            let left = workspace.active_pane().clone();
+            left.update(cx, |left, cx| left.set_min_size(size(px(300.), px(0.)), cx));
+            workspace.split_pane(left.clone(), SplitDirection::Right, cx);
+            left
+        });
+        cx.update(|cx| cx.refresh());
+
+        let left_bounds = workspace.update(cx, |workspace, _| {
+            workspace.center.bounding_box_for_pane(&left).unwrap()
+        });
+        let position = point(left_bounds.upper_right().x, left_bounds.center().y);
+        cx.simulate_event(MouseDownEvent {
+            button: MouseButton::Left,
+            position,
+            ..Default::default()
+        });
+        cx.simulate_event(MouseMoveEvent {
+            position: point(position.x - px(300.), position.y),
+            pressed_button: Some(MouseButton::Left),
+            ..Default::default()
+        });
+        cx.simulate_event(MouseUpEvent {
+            button: MouseButton::Left,
+            position: point(position.x - px(300.), position.y),
+            ..Default::default()
+        });
+        cx.update(|cx| cx.refresh());
+
+        let left_width = workspace.update(cx, |workspace, _| {
+            workspace
+                .center
+                .bounding_box_for_pane(&left)
+                .unwrap()
+                .size
+                .width
+        });
+        assert!(left_width < left_bounds.size.width, "{left_width:?}");
+        assert!(left_width >= px(299.), "{left_width:?}");
+    }
+
+    #[gpui::test]
+    async fn test_promote(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+        let row = |panes: &[&View<Pane>]| {
+            let mut group = PaneGroup::new(panes[0].clone());
+            for pair in panes.windows(2) {
+                group
+                    .split(pair[0], pair[1], SplitDirection::Right)
+                    .unwrap();
+            }
+            group
+        };
+
+        cx.update(|cx| {
+            // `b` moves past `c` and then grows into `d`.
+            let mut group = row(&[&a, &b, &c, &d]);
+            assert!(group.promote(&b, SplitDirection::Right, 0.1, cx).unwrap());
+            assert_eq!(layout(&group, &[&a, &b, &c, &d]), "h[0,2,1,3]");
+            assert_flexes(flexes_at(&group.root, &[]), &[1., 1., 1.4, 0.6]);
+
+            // Having reached the edge, `b` grows back into `c`.
+            let mut group = row(&[&a, &b, &c]);
+            group.promote(&b, SplitDirection::Right, 0.2, cx).unwrap();
+            assert_eq!(layout(&group, &[&a, &b, &c]), "h[0,2,1]");
+            assert_flexes(flexes_at(&group.root, &[]), &[1., 0.4, 1.6]);
+
+            // A pane already at the edge only grows.
+            let mut group = row(&[&a, &b, &c]);
+            assert!(group.promote(&a, SplitDirection::Left, 0.2, cx).unwrap());
+            assert_eq!(layout(&group, &[&a, &b, &c]), "h[0,1,2]");
+            assert_flexes(flexes_at(&group.root, &[]), &[1.6, 0.4, 1.]);
+
+            assert!(group.promote(&d, SplitDirection::Left, 0.2, cx).is_err());
+
+            // A lone pane has nowhere to move or grow.
+            let mut group = PaneGroup::new(a.clone());
+            assert!(!group.promote(&a, SplitDirection::Right, 0.2, cx).unwrap());
+        });
+    }
+
+    #[gpui::test]
+    async fn test_resize_active(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+
+        // -------------
+        // | a | b |   |
+        // |   | - | d |
+        // |   | c |   |
+        // -------------
+        let mut group = PaneGroup::new(a.clone());
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+        group.split(&b, &d, SplitDirection::Right).unwrap();
+        group.split(&b, &c, SplitDirection::Down).unwrap();
+        assert_eq!(layout(&group, &[&a, &b, &c, &d]), "h[0,v[1,2],3]");
+
+        cx.update(|cx| {
+            // Growing `b` rightwards resizes the outer axis, since its own is vertical.
+            assert!(group.resize_active(&b, SplitDirection::Right, 0.1, cx));
+            assert_flexes(flexes_at(&group.root, &[]), &[1., 1.3, 0.7]);
+            assert_flexes(flexes_at(&group.root, &[1]), &[1., 1.]);
+
+            group.resize_active(&c, SplitDirection::Left, 0.1, cx);
+            assert_flexes(flexes_at(&group.root, &[]), &[0.7, 1.6, 0.7]);
+
+            assert!(!group.resize_active(&b, SplitDirection::Up, 0.1, cx));
+            assert!(!group.resize_active(&a, SplitDirection::Left, 0.1, cx));
+            assert_flexes(flexes_at(&group.root, &[]), &[0.7, 1.6, 0.7]);
+
+            // Once laid out, panes can't be shrunk below the minimum size.
+            let min_width = WorkspaceSettings::get_global(cx).pane_min_width;
+            let bounds = |x: f32, width: f32| {
+                Some(Bounds {
+                    origin: point(px(x), px(0.)),
+                    size: size(px(width), px(600.)),
+                })
+            };
+            let Member::Axis(axis) = &group.root else {
+                panic!("root is not an axis");
+            };
+            *axis.bounding_boxes.lock() =
+                vec![bounds(0., 210.), bounds(210., 480.), bounds(690., 210.)];
+            group.resize_active(&d, SplitDirection::Left, 0.5, cx);
+            let flexes = flexes_at(&group.root, &[]);
+            assert!((flexes[1] * 300. - min_width).abs() < 0.5, "{flexes:?}");
+        });
+
+        // A pane's own minimum counts too, including from inside a nested axis.
+        c.update(cx, |c, cx| c.set_min_size(size(px(300.), px(0.)), cx));
+        set_flexes(&group.root, &[], vec![0.7, 1.6, 0.7]);
+        cx.update(|cx| group.resize_active(&d, SplitDirection::Left, 0.5, cx));
+        let flexes = flexes_at(&group.root, &[]);
+        assert!((flexes[1] * 300. - 300.).abs() < 0.5, "{flexes:?}");
+    }
+
+    #[gpui::test]
+    async fn test_relocate_pane(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+
+        let mut group = PaneGroup::new(a.clone());
+        assert!(!group.relocate_pane(&a, &a, SplitDirection::Up).unwrap());
+        assert_eq!(layout(&group, &[&a]), "0");
+
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+        group.split(&b, &c, SplitDirection::Right).unwrap();
+        assert_eq!(layout(&group, &[&a, &b, &c]), "h[0,1,2]");
+
+        assert!(group.relocate_pane(&c, &a, SplitDirection::Down).unwrap());
+        assert_eq!(layout(&group, &[&a, &b, &c]), "h[v[0,2],1]");
+
+        assert!(group.relocate_pane(&b, &c, SplitDirection::Left).unwrap());
+        assert_eq!(layout(&group, &[&a, &b, &c]), "v[0,h[1,2]]");
+
+        assert!(!group.relocate_pane(&b, &b, SplitDirection::Right).unwrap());
+        assert!(group.relocate_pane(&d, &a, SplitDirection::Up).is_err());
+        assert!(group.relocate_pane(&a, &d, SplitDirection::Up).is_err());
+        assert_eq!(layout(&group, &[&a, &b, &c]), "v[0,h[1,2]]");
+    }
+
+    #[gpui::test]
+    async fn test_split_grid(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d, e] = add_panes(&workspace, cx);
+
+        let mut group = PaneGroup::new(a.clone());
+        assert!(group
+            .split_grid(&a, vec![b.clone(), c.clone()], 2, 2)
+            .is_err());
+        assert_eq!(layout(&group, &[&a]), "0");
+
+        group
+            .split_grid(&a, vec![b.clone(), c.clone(), d.clone()], 2, 2)
+            .unwrap();
+        assert_eq!(layout(&group, &[&a, &b, &c, &d]), "v[h[0,1],h[2,3]]");
+        assert_eq!(flexes_at(&group.root, &[]), vec![1., 1.]);
+        assert_eq!(flexes_at(&group.root, &[0]), vec![1., 1.]);
+        assert_eq!(flexes_at(&group.root, &[1]), vec![1., 1.]);
+        assert_eq!(group.panes(), vec![&a, &b, &c, &d]);
+
+        // A single column doesn't introduce single-member rows.
+        group.split_grid(&d, vec![e.clone()], 2, 1).unwrap();
+        assert_eq!(
+            layout(&group, &[&a, &b, &c, &d, &e]),
+            "v[h[0,1],h[2,v[3,4]]]"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_remove_preserves_flexes(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c] = add_panes(&workspace, cx);
+
+        let mut group = PaneGroup::new(a.clone());
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+        group.split(&b, &c, SplitDirection::Right).unwrap();
+        set_flexes(&group.root, &[], vec![0.6, 0.9, 1.5]);
+
+        group.remove(&b).unwrap();
+        assert_eq!(layout(&group, &[&a, &b, &c]), "h[0,2]");
+        let flexes = flexes_at(&group.root, &[]);
+        assert_flexes(flexes.clone(), &[2. * 0.6 / 2.1, 2. * 1.5 / 2.1]);
+        assert!((flexes[1] / flexes[0] - 2.5).abs() < 0.001);
+    }
+
+    #[gpui::test]
+    async fn test_visit(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+
+        let mut group = grid(&a, &b, &c, &d);
+        let mut visited_panes = Vec::new();
+        let mut axes = Vec::new();
+        group.visit(|member| match member {
+            Member::Pane(pane) => visited_panes.push(pane),
+            Member::Axis(axis) => axes.push(axis.axis),
+        });
+        assert_eq!(visited_panes, group.panes());
+        assert_eq!(visited_panes, vec![&a, &c, &b, &d]);
+        assert_eq!(axes, vec![Axis::Horizontal, Axis::Vertical, Axis::Vertical]);
+
+        let mut visited = 0;
+        group.visit_mut(|_| visited += 1);
+        assert_eq!(visited, 7);
+    }
+
+    #[gpui::test]
+    async fn test_pane_paths(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d, e] = add_panes(&workspace, cx);
+
+        let group = PaneGroup::new(a.clone());
+        assert_eq!(group.path_to_pane(&a), Some(vec![]));
+        assert_eq!(group.pane_at_path(&[]), Some(&a));
+        assert_eq!(group.pane_at_path(&[0]), None);
+
+        let group = grid(&a, &b, &c, &d);
+        for pane in [&a, &b, &c, &d] {
+            let path = group.path_to_pane(pane).unwrap();
+            assert_eq!(group.pane_at_path(&path), Some(pane));
+        }
+        assert_eq!(group.path_to_pane(&c), Some(vec![0, 1]));
+        assert_eq!(group.path_to_pane(&e), None);
+
+        // Paths that no longer lead to a pane don't resolve.
+        assert_eq!(group.pane_at_path(&[]), None);
+        assert_eq!(group.pane_at_path(&[0]), None);
+        assert_eq!(group.pane_at_path(&[2, 0]), None);
+        assert_eq!(group.pane_at_path(&[0, 1, 0]), None);
+    }
+
+    #[gpui::test]
+    async fn test_pane_paths_in_nested_tree(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d, e, f] = add_panes(&workspace, cx);
+
+        let mut group = PaneGroup::new(a.clone());
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+        group.split(&b, &c, SplitDirection::Down).unwrap();
+        group.split(&c, &d, SplitDirection::Right).unwrap();
+        group.split(&d, &e, SplitDirection::Down).unwrap();
+        group.split(&a, &f, SplitDirection::Up).unwrap();
+        let panes = [&a, &b, &c, &d, &e, &f];
+        assert_eq!(layout(&group, &panes), "h[v[5,0],v[1,h[2,v[3,4]]]]");
+
+        for pane in panes {
+            let path = group.path_to_pane(pane).unwrap();
+            assert_eq!(group.pane_at_path(&path), Some(pane));
+        }
+        assert_eq!(group.path_to_pane(&e), Some(vec![1, 1, 1, 1]));
+        assert_eq!(group.path_to_pane(&f), Some(vec![0, 0]));
+    }
+
+    #[gpui::test]
+    async fn test_split_at_path(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d, e, f] = add_panes(&workspace, cx);
+        let panes = [&a, &b, &c, &d, &e, &f];
+
+        let mut group = PaneGroup::new(a.clone());
+        group.split_at_path(&[], &b, SplitDirection::Right).unwrap();
+        assert_eq!(layout(&group, &panes), "h[0,1]");
+
+        let mut group = grid(&a, &b, &c, &d);
+        group
+            .split_at_path(&[1, 1], &e, SplitDirection::Right)
+            .unwrap();
+        assert_eq!(layout(&group, &panes), "h[v[0,2],v[1,h[3,4]]]");
+        group
+            .split_at_path(&[0, 0], &f, SplitDirection::Down)
+            .unwrap();
+        assert_eq!(layout(&group, &panes), "h[v[0,5,2],v[1,h[3,4]]]");
+        assert_eq!(group.path_to_pane(&e), Some(vec![1, 1, 1]));
+
+        let error = |group: &mut PaneGroup, path: &[usize]| {
+            group
+                .split_at_path(path, &c, SplitDirection::Left)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            error(&mut group, &[1]),
+            "Path [1] leads to an axis, not a pane"
+        );
+        assert_eq!(error(&mut group, &[2]), "Path [2] doesn't lead to a member");
+        assert_eq!(
+            error(&mut group, &[0, 0, 0]),
+            "Path [0, 0, 0] doesn't lead to a member"
+        );
+        assert_eq!(layout(&group, &panes), "h[v[0,5,2],v[1,h[3,4]]]");
+    }
+
+    #[gpui::test]
+    async fn test_common_ancestor(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d, e] = add_panes(&workspace, cx);
+
+        let group = grid(&a, &b, &c, &d);
+        // Siblings in one column share their vertical axis...
+        assert_eq!(group.common_ancestor(&a, &c), Some(Axis::Vertical));
+        assert_eq!(group.common_ancestor(&d, &b), Some(Axis::Vertical));
+        // ...while panes in different columns only share the root.
+        assert_eq!(group.common_ancestor(&a, &b), Some(Axis::Horizontal));
+        assert_eq!(group.common_ancestor(&c, &d), Some(Axis::Horizontal));
+        assert_eq!(group.common_ancestor(&a, &a), Some(Axis::Vertical));
+
+        assert_eq!(group.common_ancestor(&a, &e), None);
+        assert_eq!(PaneGroup::new(a.clone()).common_ancestor(&a, &a), None);
+    }
+
+    #[gpui::test]
+    async fn test_layout_diff(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+        let base = || {
+            let mut group = PaneGroup::new(a.clone());
+            group.split(&a, &b, SplitDirection::Right).unwrap();
+            group.split(&b, &c, SplitDirection::Down).unwrap();
+            group
+        };
+        assert!(base().diff(&base()).is_empty());
+
+        // Splitting `a` wraps it in a new column below the root.
+        let mut split = base();
+        split.split(&a, &d, SplitDirection::Down).unwrap();
+        assert_eq!(layout(&split, &[&a, &b, &c, &d]), "h[v[0,3],v[1,2]]");
+        assert_eq!(
+            base().diff(&split),
+            LayoutDiff {
+                added: vec![d.clone()],
+                removed: vec![],
+                reparented: vec![a.clone()],
+            }
+        );
+
+        // Removing `c` leaves `b` alone in its column, which collapses into the root.
+        let mut removed = base();
+        removed.remove(&c).unwrap();
+        assert_eq!(layout(&removed, &[&a, &b]), "h[0,1]");
+        let diff = base().diff(&removed);
+        assert_eq!(
+            diff,
+            LayoutDiff {
+                added: vec![],
+                removed: vec![c.clone()],
+                reparented: vec![b.clone()],
+            }
+        );
+        assert_eq!(removed.diff(&base()).added, vec![c.clone()]);
+
+        // Swapping panes within an axis isn't a reparenting.
+        let mut swapped = base();
+        swapped.swap(&b, &c).unwrap();
+        assert!(base().diff(&swapped).is_empty());
+
+        // Removing an earlier sibling shifts the later ones, but leaves their axes as they were.
+        let row = || {
+            let mut group = PaneGroup::new(a.clone());
+            group.split(&a, &b, SplitDirection::Right).unwrap();
+            group.split(&b, &c, SplitDirection::Right).unwrap();
+            group.split(&c, &d, SplitDirection::Down).unwrap();
+            group
+        };
+        assert_eq!(layout(&row(), &[&a, &b, &c, &d]), "h[0,1,v[2,3]]");
+        let mut removed = row();
+        removed.remove(&a).unwrap();
+        assert_eq!(layout(&removed, &[&a, &b, &c, &d]), "h[1,v[2,3]]");
+        assert_eq!(
+            row().diff(&removed),
+            LayoutDiff {
+                added: vec![],
+                removed: vec![a.clone()],
+                reparented: vec![],
+            }
+        );
+    }
+
+    #[gpui::test]
+    async fn test_minimize_and_restore(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+
+        let mut group = PaneGroup::new(a.clone());
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+        group.split(&b, &c, SplitDirection::Right).unwrap();
+        set_flexes(&group.root, &[], vec![1.5, 0.9, 0.6]);
+
+        // The minimized pane's space goes to the others, which keep their ratio.
+        group.minimize(&b).unwrap();
+        assert!(group.is_minimized(&b));
+        let flexes = flexes_at(&group.root, &[]);
+        assert!((flexes[1] - element::MIN_FLEX).abs() < 0.0001);
+        assert!((flexes[0] / flexes[2] - 2.5).abs() < 0.001);
+        assert!((flexes.iter().sum::<f32>() - 3.).abs() < 0.001);
+
+        group.minimize(&b).unwrap();
+        assert_eq!(flexes_at(&group.root, &[]), flexes);
+
+        group.restore(&b).unwrap();
+        assert!(!group.is_minimized(&b));
+        assert_flexes(flexes_at(&group.root, &[]), &[1.5, 0.9, 0.6]);
+        assert!(group.restore(&b).is_err());
+
+        assert!(group.minimize(&d).is_err());
+        assert!(PaneGroup::new(a.clone()).minimize(&a).is_err());
+    }
+
+    #[gpui::test]
+    async fn test_insert_at(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d, e] = add_panes(&workspace, cx);
+        let mut axis = PaneAxis::new(
+            Axis::Horizontal,
+            vec![Member::Pane(a.clone()), Member::Pane(b.clone())],
+        );
+        let assert_axis = |axis: &PaneAxis, panes: &[&View<Pane>], flexes: &[f32]| {
+            let members = axis
+                .members
+                .iter()
+                .map(|member| match member {
+                    Member::Pane(pane) => pane,
+                    Member::Axis(_) => unreachable!(),
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(members, panes);
+            assert_eq!(axis.bounding_boxes.lock().len(), panes.len());
+            assert_flexes(axis.flexes(), flexes);
+        };
+
+        axis.insert_at(0, Member::Pane(c.clone()), 2.).unwrap();
+        assert_axis(&axis, &[&c, &a, &b], &[1.5, 0.75, 0.75]);
+        axis.insert_at(2, Member::Pane(d.clone()), 0.75).unwrap();
+        assert_axis(&axis, &[&c, &a, &d, &b], &[1.6, 0.8, 0.8, 0.8]);
+        axis.insert_at(4, Member::Pane(e.clone()), 0.8).unwrap();
+        assert_axis(
+            &axis,
+            &[&c, &a, &d, &b, &e],
+            &[1.6667, 0.8333, 0.8333, 0.8333, 0.8333],
+        );
+
+        assert!(axis.insert_at(6, Member::Pane(e.clone()), 1.).is_err());
+        assert_eq!(axis.members.len(), 5);
+    }
+
+    #[gpui::test]
+    async fn test_from_members(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b] = add_panes(&workspace, cx);
+
+        assert!(PaneGroup::from_members(vec![], Axis::Horizontal).is_err());
+
+        let group = PaneGroup::from_members(
+            vec![Member::Pane(a.clone()), Member::Pane(b.clone())],
+            Axis::Vertical,
+        )
+        .unwrap();
+        assert_eq!(layout(&group, &[&a, &b]), "v[0,1]");
+        assert_flexes(flexes_at(&group.root, &[]), &[1., 1.]);
+
+        let group = PaneGroup::from_members(vec![Member::Pane(a.clone())], Axis::Vertical).unwrap();
+        assert_eq!(layout(&group, &[&a]), "0");
+    }
+
+    #[gpui::test]
+    async fn test_edge_pane(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d, e] = add_panes(&workspace, cx);
+
+        // -------------
+        // | a   | b   |
+        // |-----|-----|
+        // | c   | d   |
+        // -------------
+        let group = grid(&a, &b, &c, &d);
+        assert_eq!(group.edge_pane(SplitDirection::Left), a);
+        assert_eq!(group.edge_pane(SplitDirection::Right), b);
+        assert_eq!(group.edge_pane(SplitDirection::Up), a);
+        assert_eq!(group.edge_pane(SplitDirection::Down), c);
+
+        let mut group = PaneGroup::new(a.clone());
+        assert_eq!(group.edge_pane(SplitDirection::Right), a);
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+        group.split(&b, &e, SplitDirection::Down).unwrap();
+        assert_eq!(group.edge_pane(SplitDirection::Right), b);
+        assert_eq!(group.edge_pane(SplitDirection::Down), a);
+    }
+
+    #[gpui::test]
+    async fn test_rebalance_containing(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d, e] = add_panes(&workspace, cx);
+
+        let mut group = grid(&a, &b, &c, &d);
+        set_flexes(&group.root, &[], vec![1.5, 0.5]);
+        set_flexes(&group.root, &[0], vec![1.2, 0.8]);
+        set_flexes(&group.root, &[1], vec![0.3, 1.7]);
+
+        group.rebalance_containing(&d).unwrap();
+        assert_flexes(flexes_at(&group.root, &[1]), &[1., 1.]);
+        assert_flexes(flexes_at(&group.root, &[0]), &[1.2, 0.8]);
+        assert_flexes(flexes_at(&group.root, &[]), &[1.5, 0.5]);
+
+        assert!(group.rebalance_containing(&e).is_err());
+        assert!(PaneGroup::new(a.clone()).rebalance_containing(&a).is_ok());
+        assert!(PaneGroup::new(a.clone()).rebalance_containing(&e).is_err());
+    }
+
+    #[gpui::test]
+    async fn test_tile_evenly(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+
+        let mut group = grid(&a, &b, &c, &d);
+        set_flexes(&group.root, &[], vec![1.5, 0.5]);
+        group.minimize(&b).unwrap();
+        group.tile_evenly(Axis::Vertical);
+        assert_eq!(layout(&group, &[&a, &b, &c, &d]), "v[0,2,1,3]");
+        assert_flexes(flexes_at(&group.root, &[]), &[1., 1., 1., 1.]);
+        assert!(!group.is_minimized(&b));
+
+        group.tile_evenly(Axis::Horizontal);
+        assert_eq!(layout(&group, &[&a, &b, &c, &d]), "h[0,2,1,3]");
+
+        let mut group = PaneGroup::new(a.clone());
+        group.tile_evenly(Axis::Horizontal);
+        assert_eq!(layout(&group, &[&a]), "0");
+    }
+
+    #[gpui::test]
+    async fn test_same_shape(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d, e, f, g, h] = add_panes(&workspace, cx);
+
+        let group = grid(&a, &b, &c, &d);
+        let other = grid(&e, &f, &g, &h);
+        assert!(group.same_shape(&other));
+        assert_ne!(group.panes(), other.panes());
+
+        set_flexes(&other.root, &[1], vec![0.5, 1.5]);
+        assert!(!group.same_shape(&other));
+        set_flexes(&group.root, &[1], vec![0.5, 1.5]);
+        assert!(group.same_shape(&other));
+
+        let mut rotated = grid(&e, &f, &g, &h);
+        rotated.rotate_containing(&e).unwrap();
+        assert!(!group.same_shape(&rotated));
+
+        let mut uneven = PaneGroup::new(e.clone());
+        uneven.split(&e, &f, SplitDirection::Right).unwrap();
+        assert!(!group.same_shape(&uneven));
+        assert!(!uneven.same_shape(&PaneGroup::new(g.clone())));
+        assert!(PaneGroup::new(a.clone()).same_shape(&PaneGroup::new(g.clone())));
+    }
+
+    #[gpui::test]
+    async fn test_clone_layout_with(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d, e, f, g, h] = add_panes(&workspace, cx);
+
+        let group = grid(&a, &b, &c, &d);
+        set_flexes(&group.root, &[], vec![1.2, 0.8]);
+        set_flexes(&group.root, &[1], vec![0.5, 1.5]);
+
+        let mut new_panes = vec![e.clone(), f.clone(), g.clone(), h.clone()].into_iter();
+        let copy = group.clone_layout_with(&mut || new_panes.next().unwrap());
+        assert_eq!(copy.panes(), [&e, &f, &g, &h]);
+        assert_eq!(layout(&copy, &[&e, &f, &g, &h]), "h[v[0,1],v[2,3]]");
+        assert_flexes(flexes_at(&copy.root, &[]), &[1.2, 0.8]);
+        assert_flexes(flexes_at(&copy.root, &[0]), &[1., 1.]);
+        assert_flexes(flexes_at(&copy.root, &[1]), &[0.5, 1.5]);
+
+        // The copy's flexes are its own.
+        set_flexes(&copy.root, &[], vec![0.4, 1.6]);
+        assert_flexes(flexes_at(&group.root, &[]), &[1.2, 0.8]);
+        assert_eq!(layout(&group, &[&a, &b, &c, &d]), "h[v[0,2],v[1,3]]");
+    }
+
+    #[gpui::test]
+    async fn test_map_panes(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d, e, f] = add_panes(&workspace, cx);
+
+        let mut group = PaneGroup::new(a.clone());
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+        group.split(&b, &c, SplitDirection::Down).unwrap();
+        set_flexes(&group.root, &[], vec![1.4, 0.6]);
+        set_flexes(&group.root, &[1], vec![0.5, 1.5]);
+        group.minimize(&c).unwrap();
+
+        group.map_panes(|pane| {
+            if *pane == a {
+                d.clone()
+            } else if *pane == b {
+                e.clone()
+            } else {
+                f.clone()
+            }
+        });
+
+        assert_eq!(group.panes(), [&d, &e, &f]);
+        assert_eq!(layout(&group, &[&d, &e, &f]), "h[0,v[1,2]]");
+        assert_flexes(flexes_at(&group.root, &[]), &[1.4, 0.6]);
+        assert!(group.is_minimized(&f));
+        assert!(!group.is_minimized(&c));
+        group.restore(&f).unwrap();
+        assert_flexes(flexes_at(&group.root, &[1]), &[0.5, 1.5]);
+    }
+
+    #[gpui::test]
+    async fn test_flatten(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+
+        let mut group = PaneGroup::with_root(Member::Axis(PaneAxis::load(
+            Axis::Horizontal,
+            vec![
+                Member::Pane(a.clone()),
+                Member::Axis(PaneAxis::load(
+                    Axis::Horizontal,
+                    vec![
+                        Member::Pane(b.clone()),
+                        Member::Axis(PaneAxis::load(
+                            Axis::Vertical,
+                            vec![Member::Pane(c.clone()), Member::Pane(d.clone())],
+                            Some(vec![1.5, 0.5]),
+                        )),
+                    ],
+                    Some(vec![0.5, 1.5]),
+                )),
+            ],
+            Some(vec![1.2, 0.8]),
+        )));
+        group.flatten();
+        assert_eq!(layout(&group, &[&a, &b, &c, &d]), "h[0,1,v[2,3]]");
+        assert_flexes(flexes_at(&group.root, &[]), &[1.8, 0.3, 0.9]);
+        assert_flexes(flexes_at(&group.root, &[2]), &[1.5, 0.5]);
+
+        // Removing `b` collapses its axis into a lone horizontal axis, which is merged away.
+        let mut group = PaneGroup::new(a.clone());
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+        group.split(&b, &c, SplitDirection::Down).unwrap();
+        group.split(&c, &d, SplitDirection::Right).unwrap();
+        assert_eq!(layout(&group, &[&a, &b, &c, &d]), "h[0,v[1,h[2,3]]]");
+        group.remove(&b).unwrap();
+        assert_eq!(layout(&group, &[&a, &b, &c, &d]), "h[0,2,3]");
+        assert_flexes(flexes_at(&group.root, &[]), &[1.5, 0.75, 0.75]);
+    }
+
+    #[gpui::test]
+    async fn test_layout_strings(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let panes: [View<Pane>; 28] = add_panes(&workspace, cx);
+        let resolve = |name: &str| {
+            panes
+                .iter()
+                .enumerate()
+                .find(|(ix, _)| layout_pane_name(*ix) == name)
+                .map(|(_, pane)| pane.clone())
+        };
+
+        for layout in [
+            "a",
+            "h[a,b]",
+            "h[v[a,b],c]",
+            "v[a,h[b,v[c,d],e],f]",
+            "h[v[a,b,c],v[d,h[e,f]],g]",
+        ] {
+            let group = PaneGroup::from_layout_string(layout, resolve).unwrap();
+            assert_eq!(group.to_layout_string(), layout);
+        }
+
+        let group = grid(&panes[0], &panes[1], &panes[2], &panes[3]);
+        let layout = group.to_layout_string();
+        assert_eq!(layout, "h[v[a,b],v[c,d]]");
+        let parsed = PaneGroup::from_layout_string(&layout, resolve).unwrap();
+        assert_eq!(parsed.panes(), group.panes());
+
+        let names = (0..28).map(layout_pane_name).collect::<Vec<_>>();
+        let layout = format!("h[{}]", names.join(","));
+        assert_eq!(&names[25..], ["z", "aa", "ab"]);
+        let group = PaneGroup::from_layout_string(&layout, resolve).unwrap();
+        assert_eq!(group.to_layout_string(), layout);
+        assert_eq!(group.depth(), 1);
+
+        let group = PaneGroup::from_layout_string(" h[ a , v[b,c] ] ", resolve).unwrap();
+        assert_eq!(group.to_layout_string(), "h[a,v[b,c]]");
+
+        for (layout, error) in [
+            ("", "Layout ended early, expected a pane or an axis"),
+            ("h[a,b", "Unclosed axis starting at 0"),
+            ("h[a,b]]", "Unexpected ']' at 6 in layout \"h[a,b]]\""),
+            ("x[a,b]", "Unknown axis \"x\" at 0, expected 'h' or 'v'"),
+            ("h[a]", "Axis at 0 has fewer than two members"),
+            ("h[a;b]", "Unexpected ';' at 3, expected ',' or ']'"),
+            ("h[a,,b]", "Unexpected ',' at 4, expected a pane or an axis"),
+            ("h[a,a]", "Pane \"a\" appears more than once"),
+            ("h[a,zz]", "Unknown pane \"zz\""),
+        ] {
+            let result = PaneGroup::from_layout_string(layout, resolve);
+            assert_eq!(result.err().unwrap().to_string(), error, "{layout:?}");
+        }
+    }
+
+    #[gpui::test]
+    async fn test_panes_order(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+
+        // Rows within a column read like text.
+        let mut group = PaneGroup::new(a.clone());
+        group
+            .split_grid(&a, vec![b.clone(), c.clone(), d.clone()], 2, 2)
+            .unwrap();
+        assert_eq!(group.panes(), [&a, &b, &c, &d]);
+
+        // Columns are listed one after another.
+        let group = grid(&a, &b, &c, &d);
+        assert_eq!(group.panes(), [&a, &c, &b, &d]);
+    }
+
+    #[gpui::test]
+    async fn test_serialized_roundtrip(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let panes = add_panes::<4>(&workspace, cx);
+        let [a, b, c, d] = &panes;
+
+        let mut group = grid(a, b, c, d);
+        set_flexes(&group.root, &[], vec![1.5, 0.5]);
+        set_flexes(&group.root, &[1], vec![0.8, 1.2]);
+        group.set_locked(c, true).unwrap();
+
+        // Each pane is described by a single item holding its index in `panes`.
+        let describe = |pane: &View<Pane>| {
+            let ix = panes.iter().position(|p| p == pane).unwrap();
+            SerializedPane::new(
+                vec![SerializedItem::new("TestItem", ix as u64, true)],
+                false,
+            )
+        };
+        let pane = |ix: u64, locked| {
+            SerializedPaneGroup::Pane(
+                SerializedPane::new(vec![SerializedItem::new("TestItem", ix, true)], false)
+                    .with_locked(locked),
+            )
+        };
+        let serialized = group.to_serialized(describe);
+        assert_eq!(
+            serialized,
+            SerializedPaneGroup::Group {
+                axis: SerializedAxis(Axis::Horizontal),
+                flexes: Some(vec![1.5, 0.5]),
+                children: vec![
+                    SerializedPaneGroup::Group {
+                        axis: SerializedAxis(Axis::Vertical),
+                        flexes: Some(vec![1., 1.]),
+                        children: vec![pane(0, false), pane(2, true)],
+                    },
+                    SerializedPaneGroup::Group {
+                        axis: SerializedAxis(Axis::Vertical),
+                        flexes: Some(vec![0.8, 1.2]),
+                        children: vec![pane(1, false), pane(3, false)],
+                    },
+                ],
+            }
+        );
+
+        let open =
+            |serialized: &SerializedPane| panes[serialized.children[0].item_id as usize].clone();
+        let restored = PaneGroup::from_serialized(&serialized, open).unwrap();
+        assert_eq!(
+            layout(&restored, &[a, b, c, d]),
+            layout(&group, &[a, b, c, d])
+        );
+        assert_eq!(restored.axes(), group.axes());
+        assert!(restored.is_locked(c));
+        assert!(!restored.is_locked(a));
+        assert_eq!(restored.to_serialized(describe), serialized);
+
+        // A layout that starts out serialized survives the trip through a live group too.
+        let serialized = SerializedPaneGroup::Group {
+            axis: SerializedAxis(Axis::Vertical),
+            flexes: Some(vec![0.5, 1.5]),
+            children: vec![
+                pane(3, true),
+                SerializedPaneGroup::Group {
+                    axis: SerializedAxis(Axis::Horizontal),
+                    flexes: Some(vec![1.2, 0.3, 1.5]),
+                    children: vec![pane(0, false), pane(2, false), pane(1, false)],
+                },
+            ],
+        };
+        let restored = PaneGroup::from_serialized(&serialized, open).unwrap();
+        assert_eq!(layout(&restored, &[a, b, c, d]), "v[3,h[0,2,1]]");
+        assert!(restored.is_locked(d));
+        assert_eq!(restored.to_serialized(describe), serialized);
+
+        // Empty axes are dropped, and an axis with a single member collapses onto it.
+        let serialized = SerializedPaneGroup::Group {
+            axis: SerializedAxis(Axis::Horizontal),
+            flexes: Some(vec![1., 1.]),
+            children: vec![
+                SerializedPaneGroup::Group {
+                    axis: SerializedAxis(Axis::Vertical),
+                    flexes: None,
+                    children: vec![],
+                },
+                pane(1, false),
+            ],
+        };
+        let restored = PaneGroup::from_serialized(&serialized, open).unwrap();
+        assert_eq!(layout(&restored, &[a, b]), "1");
+
+        let empty = SerializedPaneGroup::Group {
+            axis: SerializedAxis(Axis::Vertical),
+            flexes: None,
+            children: vec![],
+        };
+        assert!(PaneGroup::from_serialized(&empty, open).is_err());
+    }
+
+    #[gpui::test]
+    async fn test_axes(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+
+        assert_eq!(PaneGroup::new(a.clone()).axes(), vec![]);
+
+        let group = grid(&a, &b, &c, &d);
+        set_flexes(&group.root, &[], vec![1.5, 0.5]);
+        set_flexes(&group.root, &[1], vec![0.8, 1.2]);
+        assert_eq!(
+            group.axes(),
+            vec![
+                (Axis::Horizontal, vec![1.5, 0.5]),
+                (Axis::Vertical, vec![1., 1.]),
+                (Axis::Vertical, vec![0.8, 1.2]),
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_apply_flexes(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+
+        let source = grid(&a, &b, &c, &d);
+        set_flexes(&source.root, &[], vec![1.5, 0.5]);
+        set_flexes(&source.root, &[1], vec![0.8, 1.2]);
+        let paths = [vec![], vec![0], vec![1]];
+        let flexes_by_path: HashMap<_, _> = paths
+            .iter()
+            .map(|path| (path.clone(), axis_at(&source.root, path).flexes()))
+            .collect();
+
+        let mut group = grid(&a, &b, &c, &d);
+        group.apply_flexes(&flexes_by_path).unwrap();
+        assert_eq!(group.axes(), source.axes());
+
+        // Unlisted axes are left alone.
+        let mut flexes_by_path = HashMap::default();
+        flexes_by_path.insert(vec![0], vec![0.4, 1.6]);
+        group.apply_flexes(&flexes_by_path).unwrap();
+        assert_flexes(flexes_at(&group.root, &[]), &[1.5, 0.5]);
+        assert_flexes(flexes_at(&group.root, &[0]), &[0.4, 1.6]);
+        assert_flexes(flexes_at(&group.root, &[1]), &[0.8, 1.2]);
+
+        // A single bad entry leaves every axis unchanged.
+        flexes_by_path.insert(vec![], vec![1., 1.]);
+        flexes_by_path.insert(vec![1], vec![1., 1., 1.]);
+        let error = group.apply_flexes(&flexes_by_path).unwrap_err();
+        assert_eq!(error.to_string(), "Expected 2 flexes at [1] but got 3");
+        flexes_by_path.remove(&vec![1]);
+        flexes_by_path.insert(vec![1, 0], vec![1.]);
+        assert!(group.apply_flexes(&flexes_by_path).is_err());
+        assert_flexes(flexes_at(&group.root, &[]), &[1.5, 0.5]);
+        assert_flexes(flexes_at(&group.root, &[0]), &[0.4, 1.6]);
+    }
+
+    #[gpui::test]
+    async fn test_len_and_depth(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+
+        let mut group = PaneGroup::new(a.clone());
+        assert_eq!((group.len(), group.depth()), (1, 0));
+        assert!(!group.is_empty());
+
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+        group.split(&b, &c, SplitDirection::Right).unwrap();
+        assert_eq!((group.len(), group.depth()), (3, 1));
+
+        let group = grid(&a, &b, &c, &d);
+        assert_eq!((group.len(), group.depth()), (4, 2));
+    }
+
+    #[test]
+    fn test_active_pane_magnification() {
+        let flexes = [1.2, 0.6, 1.2];
+
+        // The active member grows relative to its neighbors.
+        let layout_flexes = element::magnified_flexes(&flexes, Some(1), 2.);
+        assert_flexes(layout_flexes.clone(), &[1., 1., 1.]);
+        assert_flexes(
+            element::unmagnified_flexes(&layout_flexes, Some(1), 2.),
+            &flexes,
+        );
+
+        // Without an active member or magnification the stored flexes are used as-is.
+        assert_eq!(
+            element::magnified_flexes(&flexes, None, 2.),
+            flexes.to_vec()
+        );
+        assert_eq!(
+            element::magnified_flexes(&flexes, Some(1), 1.),
+            flexes.to_vec()
+        );
+
+        // Resizing the magnified layout maps back onto stored flexes that stay in bounds.
+        let mut layout_flexes = element::magnified_flexes(&flexes, Some(0), 1.5);
+        element::resize_flexes(&mut layout_flexes, 0, px(500.), px(900.), &[px(80.); 3]);
+        let stored_flexes = element::unmagnified_flexes(&layout_flexes, Some(0), 1.5);
+        assert!((stored_flexes.iter().sum::<f32>() - 3.).abs() < 0.001);
+        assert!(stored_flexes[0] > flexes[0]);
+
+        // A magnification that isn't positive is clamped, so the active member stays visible
+        // and the layout can still be mapped back.
+        for magnification in [0., -2., f32::NAN] {
+            let layout_flexes = element::magnified_flexes(&flexes, Some(1), magnification);
+            assert!(
+                layout_flexes.iter().all(|flex| *flex > 0.),
+                "{layout_flexes:?}"
+            );
+            assert_flexes(
+                element::unmagnified_flexes(&layout_flexes, Some(1), magnification),
+                &flexes,
+            );
+        }
+    }
+
+    #[test]
+    fn test_hovered_handle() {
+        let bounds = |x, y, width, height| Bounds {
+            origin: point(px(x), px(y)),
+            size: size(px(width), px(height)),
+        };
+        // A column's handle crossing the edge of the row it's in.
+        let handles = [
+            (0, bounds(98., 0., 4., 200.)),
+            (1, bounds(0., 98., 100., 4.)),
+            (1, bounds(100., 98., 100., 4.)),
+        ];
+
+        assert_eq!(
+            element::hovered_handle(&handles, point(px(99.), px(50.))),
+            Some(0)
+        );
+        assert_eq!(
+            element::hovered_handle(&handles, point(px(99.), px(100.))),
+            Some(1)
+        );
+        assert_eq!(
+            element::hovered_handle(&handles, point(px(100.), px(100.))),
+            Some(2)
+        );
+        assert_eq!(
+            element::hovered_handle(&handles, point(px(50.), px(50.))),
+            None
+        );
+
+        // Of equally deep handles, the last one painted wins.
+        let handles = [(1, bounds(0., 0., 10., 10.)), (1, bounds(5., 5., 10., 10.))];
+        assert_eq!(
+            element::hovered_handle(&handles, point(px(7.), px(7.))),
+            Some(1)
+        );
+    }
+
+    #[gpui::test]
+    async fn test_hovered_handle_in_group(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c, d] = add_panes(&workspace, cx);
+        let group = grid(&a, &b, &c, &d);
+        let bounds = |x, y, width, height| {
+            Some(Bounds {
+                origin: point(px(x), px(y)),
+                size: size(px(width), px(height)),
+            })
+        };
+        *axis_at(&group.root, &[]).bounding_boxes.lock() =
+            vec![bounds(0., 0., 100., 200.), bounds(100., 0., 100., 200.)];
+        *axis_at(&group.root, &[0]).bounding_boxes.lock() =
+            vec![bounds(0., 0., 100., 100.), bounds(0., 100., 100., 100.)];
+        *axis_at(&group.root, &[1]).bounding_boxes.lock() =
+            vec![bounds(100., 0., 100., 100.), bounds(100., 100., 100., 100.)];
+
+        let hovered = |position, cx: &mut VisualTestContext| {
+            cx.update(|cx| group.update_hovered_handle(position, cx));
+            let paths: [&[usize]; 3] = [&[], &[0], &[1]];
+            paths.map(|path| *axis_at(&group.root, path).hovered_handle.lock())
+        };
+        assert_eq!(hovered(point(px(99.), px(50.)), cx), [Some(0), None, None]);
+        // Where the handles cross, only the column's handle is hovered.
+        assert_eq!(hovered(point(px(99.), px(100.)), cx), [None, Some(0), None]);
+        assert_eq!(
+            hovered(point(px(101.), px(100.)), cx),
+            [None, None, Some(0)]
+        );
+        assert_eq!(hovered(point(px(50.), px(50.)), cx), [None, None, None]);
+
+        let claims = |path: &[usize], position, cx: &mut VisualTestContext| {
+            let flexes = axis_at(&group.root, path).flexes.clone();
+            cx.update(|cx| group.claims_handle(&flexes, 0, position, cx))
+        };
+        assert!(claims(&[0], point(px(99.), px(100.)), cx));
+        assert!(!claims(&[], point(px(99.), px(100.)), cx));
+        assert!(claims(&[], point(px(99.), px(50.)), cx));
+    }
+
+    #[test]
+    fn test_handle_bounds() {
+        let pane_bounds = Bounds {
+            origin: point(px(10.), px(20.)),
+            size: size(px(300.), px(200.)),
+        };
+
+        assert_eq!(
+            element::handle_bounds(Axis::Horizontal, pane_bounds, px(4.), px(0.)),
+            Bounds {
+                origin: point(px(308.), px(20.)),
+                size: size(px(4.), px(200.)),
+            }
+        );
+        assert_eq!(
+            element::handle_bounds(Axis::Vertical, pane_bounds, px(6.), px(0.)),
+            Bounds {
+                origin: point(px(10.), px(217.)),
+                size: size(px(300.), px(6.)),
+            }
+        );
+
+        // The handle is centered on the gap after the pane.
+        assert_eq!(
+            element::handle_bounds(Axis::Horizontal, pane_bounds, px(4.), px(10.)),
+            Bounds {
+                origin: point(px(313.), px(20.)),
+                size: size(px(4.), px(200.)),
+            }
+        );
+    }
+
+    #[test]
+    fn test_divider_color() {
+        let mut colors = ThemeColors::dark();
+        colors.horizontal_pane_divider = gpui::red();
+        colors.vertical_pane_divider = gpui::blue();
+
+        assert_eq!(
+            element::divider_color(Axis::Horizontal, &colors),
+            gpui::red()
+        );
+        assert_eq!(
+            element::divider_color(Axis::Vertical, &colors),
+            gpui::blue()
+        );
+    }
+
+    #[test]
+    fn test_equalize_adjacent() {
+        let mut flexes = vec![0.5, 1.9, 0.6];
+        element::equalize_adjacent(&mut flexes, 1);
+        assert_flexes(flexes.clone(), &[0.5, 1.25, 1.25]);
+
+        element::equalize_adjacent(&mut flexes, 0);
+        assert_flexes(flexes, &[0.875, 0.875, 1.25]);
+    }
+
+    #[test]
+    fn test_split_direction_opposite_and_rotation() {
+        use SplitDirection::*;
+
+        for (direction, opposite, clockwise) in [
+            (Up, Down, Right),
+            (Down, Up, Left),
+            (Left, Right, Up),
+            (Right, Left, Down),
+        ] {
+            assert_eq!(direction.opposite(), opposite);
+            assert_eq!(direction.rotate_clockwise(), clockwise);
+            assert_eq!(direction.opposite().opposite(), direction);
+            assert_eq!(direction.opposite().axis(), direction.axis());
+            assert_ne!(direction.rotate_clockwise().axis(), direction.axis());
+        }
+    }
+
+    #[test]
+    fn test_normalize_flexes() {
+        let container_size = px(900.);
+        let mut flexes = vec![1., 1., 1.];
+        for (ix, target_size) in [(0, 412.3), (1, 97.1), (0, 150.7), (1, 633.9), (0, 81.)]
+            .into_iter()
+            .cycle()
+            .take(50)
+        {
+            let min_sizes = [px(80.); 3];
+            element::resize_flexes(&mut flexes, ix, px(target_size), container_size, &min_sizes);
+            element::normalize_flexes(&mut flexes);
+            assert!((flexes.iter().sum::<f32>() - 3.).abs() < 0.001);
+        }
+
+        let mut flexes = vec![f32::NAN, -1., 2.];
+        element::normalize_flexes(&mut flexes);
+        assert!(flexes.iter().all(|flex| flex.is_finite() && *flex > 0.));
+        assert!((flexes.iter().sum::<f32>() - 3.).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_snap_to_tenth() {
+        let container_size = px(1000.);
+        assert_eq!(element::snap_to_tenth(px(480.), container_size), px(500.));
+        assert_eq!(element::snap_to_tenth(px(331.), container_size), px(300.));
+
+        let mut flexes = vec![1., 1.];
+        let target_size = element::snap_to_tenth(px(480.), container_size);
+        element::resize_flexes(&mut flexes, 0, target_size, container_size, &[px(80.); 2]);
+        assert_flexes(flexes, &[1., 1.]);
+
+        // Snapping doesn't get around the minimum size.
+        let mut flexes = vec![1., 1.];
+        let target_size = element::snap_to_tenth(px(960.), container_size);
+        element::resize_flexes(&mut flexes, 0, target_size, container_size, &[px(80.); 2]);
+        assert_flexes(flexes, &[1.84, 0.16]);
+    }
+
+    #[test]
+    fn test_status_box_corner() {
+        for (corner, top, left) in [
+            (StatusBoxCorner::TopLeft, true, true),
+            (StatusBoxCorner::TopRight, true, false),
+            (StatusBoxCorner::BottomLeft, false, true),
+            (StatusBoxCorner::BottomRight, false, false),
+        ] {
+            let mut status_box = corner.position(div());
+            let inset = &status_box.style().inset;
+            assert_eq!(inset.top.is_some(), top, "{corner:?}");
+            assert_eq!(inset.bottom.is_some(), !top, "{corner:?}");
+            assert_eq!(inset.left.is_some(), left, "{corner:?}");
+            assert_eq!(inset.right.is_some(), !left, "{corner:?}");
+        }
+    }
+
+    #[test]
+    fn test_fit_to_min_size() {
+        let min_size = px(100.);
+        let min_sizes = [min_size; 3];
+        let flexes = [2.2, 0.6, 0.2];
+
+        // There's room for every pane as it is.
+        assert_flexes(
+            element::fit_to_min_size(&flexes, px(1500.), &min_sizes),
+            &flexes,
+        );
+
+        // The smallest pane would be 60px, so it takes space from the others.
+        let fitted = element::fit_to_min_size(&flexes, px(900.), &min_sizes);
+        assert_flexes(fitted, &[2.0833, 0.5833, 0.3333]);
+        for container_size in [px(900.), px(400.), px(300.)] {
+            let fitted = element::fit_to_min_size(&flexes, container_size, &min_sizes);
+            assert!((fitted.iter().sum::<f32>() - 3.).abs() < 0.001);
+            for flex in fitted {
+                assert!(container_size * (flex / 3.) >= min_size - px(0.01));
+            }
+        }
+
+        // Past that point the panes can't all fit, so they share the space evenly.
+        assert_flexes(
+            element::fit_to_min_size(&flexes, px(250.), &min_sizes),
+            &[1., 1., 1.],
+        );
+    }
+
+    #[test]
+    fn test_interpolate_flexes() {
+        let from = [0.01, 1.99];
+        let to = [1., 1.];
+        assert_eq!(element::interpolate_flexes(&from, &to, 0.), from.to_vec());
+
+        let steps = 8;
+        let mut previous = from.to_vec();
+        for step in 1..=steps {
+            let flexes = element::interpolate_flexes(&from, &to, step as f32 / steps as f32);
+            assert!(
+                (flexes.iter().sum::<f32>() - 2.).abs() < 0.001,
+                "{flexes:?}"
+            );
+            assert!(flexes[0] > previous[0], "{flexes:?}");
+            previous = flexes;
+        }
+        assert_flexes(previous, &to);
+        // Overshooting the duration holds the target.
+        assert_flexes(element::interpolate_flexes(&from, &to, 1.5), &to);
+    }
+
+    #[gpui::test]
+    async fn test_split_and_remove_animation(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        let [a, b, c] = add_panes(&workspace, cx);
+        let animation_from = |group: &PaneGroup| {
+            axis_at(&group.root, &[])
+                .animation
+                .lock()
+                .as_ref()
+                .map(|animation| animation.from.clone())
+        };
+
+        // The new pane grows in from nothing, while the others keep their sizes at first.
+        let mut group = PaneGroup::new(a.clone());
+        group.split(&a, &b, SplitDirection::Right).unwrap();
+        assert_flexes(animation_from(&group).unwrap(), &[2. / 1.01, 0.02 / 1.01]);
+        group.split(&b, &c, SplitDirection::Left).unwrap();
+        assert_eq!(layout(&group, &[&a, &b, &c]), "h[0,2,1]");
+        assert_flexes(
+            animation_from(&group).unwrap(),
+            &[3. / 2.01, 0.03 / 2.01, 3. / 2.01],
+        );
+        assert_eq!(flexes_at(&group.root, &[]), vec![1., 1., 1.]);
+
+        // A removed pane's space goes to the neighbor that takes focus, and is then shared out.
+        group.remove(&c).unwrap();
+        assert_flexes(animation_from(&group).unwrap(), &[4. / 3., 2. / 3.]);
+        group.split(&a, &c, SplitDirection::Right).unwrap();
+        group.remove(&a).unwrap();
+        assert_eq!(layout(&group, &[&a, &b, &c]), "h[2,1]");
+        assert_flexes(animation_from(&group).unwrap(), &[4. / 3., 2. / 3.]);
+    }
+
+    #[test]
+    fn test_scrolled_offsets() {
+        let child_sizes = [px(100.), px(100.), px(100.)];
+        assert_eq!(
+            element::scrolled_offsets(&child_sizes, px(250.), px(0.), px(0.)),
+            (vec![px(0.), px(100.), px(200.)], px(0.))
+        );
+        assert_eq!(
+            element::scrolled_offsets(&child_sizes, px(250.), px(0.), px(30.)),
+            (vec![px(-30.), px(70.), px(170.)], px(30.))
+        );
+
+        // Scrolling stops at the ends of the overflow.
+        assert_eq!(
+            element::scrolled_offsets(&child_sizes, px(250.), px(0.), px(80.)),
+            (vec![px(-50.), px(50.), px(150.)], px(50.))
+        );
+        assert_eq!(
+            element::scrolled_offsets(&child_sizes, px(250.), px(0.), px(-10.)).1,
+            px(0.)
+        );
+        assert_eq!(
+            element::scrolled_offsets(&child_sizes, px(400.), px(0.), px(30.)),
+            (vec![px(0.), px(100.), px(200.)], px(0.))
+        );
+
+        // Gaps are left between children, and count towards the overflow.
+        assert_eq!(
+            element::scrolled_offsets(&child_sizes, px(250.), px(10.), px(100.)),
+            (vec![px(-70.), px(40.), px(150.)], px(70.))
+        );
+    }
+
+    #[test]
+    fn test_split_percentages() {
+        assert_eq!(element::split_percentages(&[1.2, 0.8], 0), "60% / 40%");
+        assert_eq!(element::split_percentages(&[600., 300.], 0), "67% / 33%");
+        assert_eq!(element::split_percentages(&[1., 1., 1.], 1), "33% / 33%");
+        assert_eq!(
+            element::split_percentages(&[0.5, 0.25, 1.25], 1),
+            "13% / 63%"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_keyboard_resize(cx: &mut TestAppContext) {
+        let (workspace, cx) = init_test(cx).await;
+        cx.simulate_resize(size(px(800.), px(600.)));
+        let [a, b, c] = workspace.update(cx, |workspace, cx| {
+            let a = workspace.active_pane().clone();
+            let b = workspace.split_pane(a.clone(), SplitDirection::Right, cx);
+            let c = workspace.split_pane(b.clone(), SplitDirection::Right, cx);
+            [a, b, c]
+        });
+        cx.update(|cx| cx.refresh());
+
+        // Each divider gets its own focus handle, and panes find the one beside them.
+        let dividers = workspace.update(cx, |workspace, _| {
+            let dividers = axis_at(&workspace.center.root, &[])
+                .divider_focus
+                .lock()
+                .clone();
+            let center = &workspace.center;
+            assert_eq!(center.divider_focus_handle(&a).as_ref(), Some(&dividers[0]));
+            assert_eq!(center.divider_focus_handle(&b).as_ref(), Some(&dividers[1]));
+            assert_eq!(center.divider_focus_handle(&c).as_ref(), Some(&dividers[1]));
+            dividers
+        });
+        assert_eq!(dividers.len(), 2);
+        assert!(dividers[0] != dividers[1]);
+        assert_eq!(
+            element::divider_label(0),
+            "Resize divider between pane 1 and 2"
+        );
+        assert_ne!(element::divider_label(0), element::divider_label(1));
+
+        // Arrow keys across the axis are ignored, those along it move the divider.
+        cx.update(|cx| cx.focus(&dividers[0]));
+        cx.update(|cx| cx.refresh());
+        cx.simulate_keystrokes("up");
+        let flexes = workspace.update(cx, |workspace, _| flexes_at(&workspace.center.root, &[]));
+        assert_flexes(flexes, &[1., 1., 1.]);
+        cx.simulate_keystrokes("right");
+        let flexes = workspace.update(cx, |workspace, _| flexes_at(&workspace.center.root, &[]));
+        assert!(flexes[0] > 1. && flexes[1] < 1.);
+        assert!((flexes[2] - 1.).abs() < 0.001);
+
+        cx.simulate_keystrokes("escape");
+        assert!(!cx.update(|cx| dividers[0].is_focused(cx)));
+
+        assert_eq!(
+            element::keyboard_resize_step(Axis::Vertical, "up"),
+            Some(px(-10.))
+        );
+        assert_eq!(element::keyboard_resize_step(Axis::Vertical, "left"), None);
+    }
+
+    #[test]
+    fn test_scroll_resize() {
+        assert_eq!(element::scroll_resize_step(point(px(0.), px(3.))), px(-10.));
+        assert_eq!(element::scroll_resize_step(point(px(0.), px(-3.))), px(10.));
+        assert_eq!(element::scroll_resize_step(point(px(-5.), px(2.))), px(10.));
+        assert_eq!(element::scroll_resize_step(point(px(0.), px(0.))), px(0.));
+
+        // Each step moves the divider by the same distance until the min size stops it.
+        let container_size = px(400.);
+        let min_sizes = [px(80.); 2];
+        let mut flexes = vec![1., 1.];
+        let step = element::scroll_resize_step(point(px(0.), px(-1.)));
+        let size = container_size / 2. * flexes[0];
+        assert!(element::resize_flexes(
+            &mut flexes,
+            0,
+            size + step,
+            container_size,
+            &min_sizes
+        ));
+        assert_flexes(flexes.clone(), &[1.05, 0.95]);
+        for _ in 0..20 {
+            let size = container_size / 2. * flexes[0];
+            element::resize_flexes(&mut flexes, 0, size + step, container_size, &min_sizes);
+        }
+        assert_flexes(flexes, &[1.6, 0.4]);
+
+        let modifiers = Modifiers {
+            alt: true,
+            ..Default::default()
+        };
+        assert!(ScrollResizeModifier::Alt.is_held(&modifiers));
+        assert!(!ScrollResizeModifier::Control.is_held(&modifiers));
+        assert!(!ScrollResizeModifier::Off.is_held(&modifiers));
+    }
+
+    #[test]
+    fn test_child_min_size_floor() {
+        let min_sizes = element::child_min_sizes(px(0.), &[px(0.), px(120.)], &[], 3);
+        assert_eq!(min_sizes, [px(16.), px(120.), px(16.)]);
+        assert_eq!(
+            element::child_min_sizes(px(200.), &[px(0.), px(120.)], &[false, true], 2),
+            [px(200.), px(16.)]
+        );
+
+        // A vanishingly small flex still gets the floor once laid out.
+        let container_size = px(900.);
+        let fitted = element::fit_to_min_size(&[0.0001, 1.4999, 1.5], container_size, &min_sizes);
+        let size = |ix: usize| element::space_per_flex(container_size, 3) * fitted[ix];
+        assert!((size(0) - px(16.)).abs() < px(0.01));
+        assert!((fitted.iter().sum::<f32>() - 3.).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_space_per_flex() {
+        assert_eq!(element::space_per_flex(px(900.), 3), px(300.));
+        for container_size in [px(f32::INFINITY), px(f32::NAN)] {
+            let space = element::space_per_flex(container_size, 3);
+            assert!(space.0.is_finite() && space > px(0.));
+        }
+    }
+
+    #[test]
+    fn test_along_edge_fraction() {
+        let bounds = Bounds {
+            origin: point(px(10.), px(20.)),
+            size: size(px(200.), px(100.)),
+        };
+        let edge = |direction: SplitDirection, fraction: f32| {
+            let edge = direction.along_edge_fraction(bounds, fraction);
+            (
+                edge.origin.x.0,
+                edge.origin.y.0,
+                edge.size.width.0,
+                edge.size.height.0,
+            )
+        };
+
+        assert_eq!(edge(SplitDirection::Up, 0.5), (10., 20., 200., 50.));
+        assert_eq!(edge(SplitDirection::Down, 0.5), (10., 70., 200., 50.));
+        assert_eq!(edge(SplitDirection::Left, 0.5), (10., 20., 100., 100.));
+        assert_eq!(edge(SplitDirection::Right, 0.25), (160., 20., 50., 100.));
+
+        assert_eq!(edge(SplitDirection::Right, 1.5), (10., 20., 200., 100.));
+        assert_eq!(edge(SplitDirection::Down, -1.), (10., 120., 200., 0.));
+    }
+
+    #[test]
+    fn test_direction_from_vector() {
+        let direction = |x, y| SplitDirection::from_vector(point(px(x), px(y)));
+
+        assert_eq!(direction(30., 10.), SplitDirection::Right);
+        assert_eq!(direction(-30., 10.), SplitDirection::Left);
+        assert_eq!(direction(10., -30.), SplitDirection::Up);
+        assert_eq!(direction(-10., 30.), SplitDirection::Down);
+
+        // Ties go to the horizontal direction.
+        assert_eq!(direction(20., -20.), SplitDirection::Right);
+        assert_eq!(direction(-20., 20.), SplitDirection::Left);
+        assert_eq!(direction(0., 0.), SplitDirection::Right);
+    }
+
+    #[test]
+    fn test_along_edge() {
+        let bounds = Bounds {
+            origin: point(px(10.), px(20.)),
+            size: size(px(400.), px(200.)),
+        };
+        let edge = |direction: SplitDirection| direction.along_edge(bounds, px(50.));
+        let rect = |x, y, width, height| Bounds {
+            origin: point(px(x), px(y)),
+            size: size(px(width), px(height)),
+        };
+
+        assert_eq!(edge(SplitDirection::Up), rect(10., 20., 400., 50.));
+        assert_eq!(edge(SplitDirection::Down), rect(10., 170., 400., 50.));
+        assert_eq!(edge(SplitDirection::Left), rect(10., 20., 50., 200.));
+        // The right edge spans the full height, starting from the top of `bounds`.
+        assert_eq!(edge(SplitDirection::Right), rect(360., 20., 50., 200.));
+    }
+
+    #[test]
+    fn test_drop_position_direction() {
+        let bounds = Bounds {
+            origin: point(px(0.), px(0.)),
+            size: size(px(400.), px(200.)),
+        };
+        let direction = |x, y| SplitDirection::for_drop_position(bounds, point(px(x), px(y)));
+
+        assert_eq!(direction(200., 10.), Some(SplitDirection::Up));
+        assert_eq!(direction(200., 190.), Some(SplitDirection::Down));
+        assert_eq!(direction(50., 100.), Some(SplitDirection::Left));
+        assert_eq!(direction(350., 100.), Some(SplitDirection::Right));
+        assert_eq!(direction(200., 100.), None);
+        assert_eq!(direction(500., 100.), None);
+    }
+
+    #[test]
+    fn test_resize_respects_min_size() {
+        let container_size = px(400.);
+        let size = |flexes: &[f32], ix: usize| container_size * (flexes[ix] / flexes.len() as f32);
+
+        // Repeatedly drag the handle towards the leading edge, as mouse moves would.
+        let mut flexes = vec![1., 1.];
+        for _ in 0..20 {
+            element::resize_flexes(&mut flexes, 0, px(50.), container_size, &[px(150.); 2]);
+        }
+        assert!(size(&flexes, 0) >= px(149.));
+        assert!(size(&flexes, 0) < px(151.));
+
+        let mut flexes = vec![1., 1.];
+        for _ in 0..20 {
+            element::resize_flexes(&mut flexes, 0, px(50.), container_size, &[px(80.); 2]);
+        }
+        assert!(size(&flexes, 0) < px(149.));
+        assert!(size(&flexes, 0) >= px(79.));
+
+        // Panes that are already below the minimum can't be resized at all.
+        let mut flexes = vec![0.5, 1.5];
+        assert!(!element::resize_flexes(
+            &mut flexes,
+            0,
+            px(50.),
+            container_size,
+            &[px(150.); 2]
+        ));
+        assert_eq!(flexes, vec![0.5, 1.5]);
+    }
+
+    #[test]
+    fn test_per_child_min_sizes() {
+        let container_size = px(800.);
+        let size = |flexes: &[f32], ix: usize| container_size * (flexes[ix] / flexes.len() as f32);
+        let min_sizes = [px(300.), px(80.)];
+
+        // Each side of the handle stops at its own minimum.
+        let mut flexes = vec![1., 1.];
+        for _ in 0..20 {
+            element::resize_flexes(&mut flexes, 0, px(50.), container_size, &min_sizes);
+        }
+        assert!((size(&flexes, 0) - px(300.)).abs() < px(1.));
+        let mut flexes = vec![1., 1.];
+        element::resize_flexes(&mut flexes, 0, px(780.), container_size, &min_sizes);
+        assert!((size(&flexes, 1) - px(80.)).abs() < px(1.));
+
+        // Layout grows a child up to its own minimum, even when the others are above theirs.
+        assert_flexes(
+            element::fit_to_min_size(&[1., 1.], container_size, &[px(500.), px(80.)]),
+            &[1.25, 0.75],
+        );
+        assert_flexes(
+            element::fit_to_min_size(&[1., 1.], container_size, &[px(500.), px(400.)]),
+            &[1., 1.],
+        );
+    }
+
+    #[test]
+    fn test_resize_cascades_across_panes() {
+        let container_size = px(900.);
+        let size = |flexes: &[f32], ix: usize| container_size * (flexes[ix] / flexes.len() as f32);
+        let assert_sizes = |flexes: &[f32], expected: [f32; 3]| {
+            for (ix, expected_size) in expected.into_iter().enumerate() {
+                assert!(
+                    (size(flexes, ix) - px(expected_size)).abs() < px(0.5),
+                    "flexes {flexes:?} don't give sizes {expected:?}"
+                );
+            }
+        };
+
+        // Dragging past the middle pane's minimum size shrinks the third pane.
+        let mut flexes = vec![1., 1., 1.];
+        assert!(element::resize_flexes(
+            &mut flexes,
+            0,
+            px(700.),
+            container_size,
+            &[px(80.); 3]
+        ));
+        assert_sizes(&flexes, [700., 80., 120.]);
+
+        // Shrinking cascades backwards, handing the space to the pane after the handle.
+        let mut flexes = vec![1., 1., 1.];
+        element::resize_flexes(&mut flexes, 1, px(20.), container_size, &[px(80.); 3]);
+        assert_sizes(&flexes, [240., 80., 580.]);
+    }
+
+    async fn init_test(cx: &mut TestAppContext) -> (View<Workspace>, &mut VisualTestContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(LoadThemes::JustBase, cx);
+            crate::init_settings(cx);
+            Project::init_settings(cx);
+            // Lay panes out at their final sizes as soon as they're split or removed
+            cx.update_global(|settings: &mut SettingsStore, cx| {
+                settings.update_user_settings::<WorkspaceSettings>(cx, |settings| {
+                    settings.animate_pane_splits = Some(false);
+                })
+            });
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, None, cx).await;
+        cx.add_window_view(|cx| Workspace::test_new(project, cx))
+    }
+
+    fn add_panes<const N: usize>(
+        workspace: &View<Workspace>,
+        cx: &mut VisualTestContext,
+    ) -> [View<Pane>; N] {
+        workspace.update(cx, |workspace, cx| {
+            std::array::from_fn(|_| workspace.add_pane(cx))
+        })
+    }
+
+    fn grid(
+        top_left: &View<Pane>,
+        top_right: &View<Pane>,
+        bottom_left: &View<Pane>,
+        bottom_right: &View<Pane>,
+    ) -> PaneGroup {
+        let mut group = PaneGroup::new(top_left.clone());
+        group
+            .split(top_left, top_right, SplitDirection::Right)
+            .unwrap();
+        group
+            .split(top_left, bottom_left, SplitDirection::Down)
+            .unwrap();
+        group
+            .split(top_right, bottom_right, SplitDirection::Down)
+            .unwrap();
+        group
+    }
+
+    /// Renders the tree as `h[..]`/`v[..]` axes around the indices of `panes`.
+    fn layout(group: &PaneGroup, panes: &[&View<Pane>]) -> String {
+        fn write_member(member: &Member, panes: &[&View<Pane>], out: &mut String) {
+            match member {
+                Member::Pane(pane) => {
+                    let ix = panes.iter().position(|p| *p == pane);
+                    out.push_str(&ix.map_or("?".to_string(), |ix| ix.to_string()));
+                }
+                Member::Axis(axis) => {
+                    out.push_str(match axis.axis {
+                        Axis::Horizontal => "h[",
+                        Axis::Vertical => "v[",
+                    });
+                    for (ix, member) in axis.members.iter().enumerate() {
+                        if ix > 0 {
+                            out.push(',');
+                        }
+                        write_member(member, panes, out);
+                    }
+                    out.push(']');
+                }
+            }
+        }
+
+        let mut out = String::new();
+        write_member(&group.root, panes, &mut out);
+        out
+    }
+
+    fn axis_at<'a>(member: &'a Member, path: &[usize]) -> &'a PaneAxis {
+        match (member, path.split_first()) {
+            (Member::Axis(axis), None) => axis,
+            (Member::Axis(axis), Some((ix, rest))) => axis_at(&axis.members[*ix], rest),
+            (Member::Pane(_), _) => panic!("no axis at path {path:?}"),
+        }
+    }
+
+    fn flexes_at(member: &Member, path: &[usize]) -> Vec<f32> {
+        axis_at(member, path).flexes.lock().clone()
+    }
+
+    fn assert_flexes(actual: Vec<f32>, expected: &[f32]) {
+        assert_eq!(actual.len(), expected.len(), "{actual:?} != {expected:?}");
+        for (actual_flex, expected_flex) in actual.iter().zip(expected) {
+            assert!(
+                (actual_flex - expected_flex).abs() < 0.001,
+                "{actual:?} != {expected:?}"
+            );
+        }
+    }
+
+    fn set_flexes(member: &Member, path: &[usize], flexes: Vec<f32>) {
+        *axis_at(member, path).flexes.lock() = flexes;
     }
 }
@@ -1,10 +1,14 @@
 pub mod model;
 
-use std::path::Path;
+use std::{
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use anyhow::{anyhow, bail, Context, Result};
 use db::{define_connection, query, sqlez::connection::Connection, sqlez_macros::sql};
 use gpui::{point, size, Axis, Bounds, WindowBounds};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use sqlez::{
     bindable::{Bind, Column, StaticColumnCount},
@@ -17,14 +21,33 @@ use uuid::Uuid;
 use crate::WorkspaceId;
 
 use model::{
-    GroupId, PaneId, SerializedItem, SerializedPane, SerializedPaneGroup, SerializedWorkspace,
-    WorkspaceLocation,
+    GroupId, LayoutStats, PaneId, SerializedItem, SerializedPane, SerializedPaneGroup,
+    SerializedWorkspace, WorkspaceLocation,
 };
 
 use self::model::DockStructure;
 
 #[derive(Copy, Clone, Debug, PartialEq)]
-pub(crate) struct SerializedAxis(pub(crate) gpui::Axis);
+pub struct SerializedAxis(pub gpui::Axis);
+
+impl Serialize for SerializedAxis {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(match self.0 {
+            Axis::Horizontal => "horizontal",
+            Axis::Vertical => "vertical",
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for SerializedAxis {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        match String::deserialize(deserializer)?.as_str() {
+            "horizontal" => Ok(Self(Axis::Horizontal)),
+            "vertical" => Ok(Self(Axis::Vertical)),
+            axis => Err(serde::de::Error::custom(format!("Unknown axis {axis}"))),
+        }
+    }
+}
 impl sqlez::bindable::StaticColumnCount for SerializedAxis {}
 impl sqlez::bindable::Bind for SerializedAxis {
     fn bind(
@@ -155,6 +178,9 @@ define_connection! {
     //   window_width: Option<f32>, // WindowBounds::Fixed RectF width
     //   window_height: Option<f32>, // WindowBounds::Fixed RectF height
     //   display: Option<Uuid>, // Display id
+    //   zoomed_pane: Option<Vec<usize>>, // A JSON array of member indices
+    //   last_opened: Option<i64>, // Unix timestamp in seconds
+    //   active_pane: Option<Vec<usize>>, // A JSON array of member indices
     // )
     //
     // pane_groups(
@@ -170,6 +196,7 @@ define_connection! {
     //     pane_id: usize, // Primary key for panes
     //     workspace_id: usize, // References workspaces table
     //     active: bool,
+    //     locked: bool,
     // )
     //
     // center_panes(
@@ -291,10 +318,66 @@ define_connection! {
     // Add pane group flex data
     sql!(
         ALTER TABLE pane_groups ADD COLUMN flexes TEXT;
-    )
+    ),
+    // Add zoomed center pane persistence
+    sql!(
+        ALTER TABLE workspaces ADD COLUMN zoomed_pane TEXT;
+    ),
+    // Add the time each workspace was last opened
+    sql!(
+        ALTER TABLE workspaces ADD COLUMN last_opened INTEGER;
+    ),
+    // Add active center pane persistence
+    sql!(
+        ALTER TABLE workspaces ADD COLUMN active_pane TEXT;
+    ),
+    // Add dock size persistence
+    sql!(
+        ALTER TABLE workspaces ADD COLUMN left_dock_size REAL;
+        ALTER TABLE workspaces ADD COLUMN right_dock_size REAL;
+        ALTER TABLE workspaces ADD COLUMN bottom_dock_size REAL;
+    ),
+    // Add locked pane persistence
+    sql!(
+        ALTER TABLE panes ADD COLUMN locked INTEGER NOT NULL DEFAULT 0; // Boolean
+    ),
+    LEGACY_LAYOUT_MIGRATION,
     ];
 }
 
+// Early versions only stored the dock pane of a workspace, with no center pane or pane
+// groups. Those workspaces get their dock pane as the root of the center group, so its
+// items aren't lost. Workspaces that already have a center layout are left alone, so
+// running it again changes nothing.
+const LEGACY_LAYOUT_MIGRATION: &str = sql!(
+    INSERT INTO center_panes(pane_id, parent_group_id, position)
+    SELECT workspaces.dock_pane, NULL, NULL
+    FROM workspaces
+    JOIN panes ON panes.pane_id = workspaces.dock_pane
+        AND panes.workspace_id = workspaces.workspace_id
+    WHERE NOT EXISTS (
+        SELECT 1 FROM pane_groups
+        WHERE pane_groups.workspace_id = workspaces.workspace_id
+    ) AND NOT EXISTS (
+        SELECT 1 FROM center_panes
+        JOIN panes AS center ON center.pane_id = center_panes.pane_id
+        WHERE center.workspace_id = workspaces.workspace_id
+    );
+);
+
+/// The columns of the `workspaces` table that make up a [`SerializedWorkspace`], apart from
+/// its center group, which is stored in `pane_groups` and `panes`.
+type WorkspaceRow = (
+    WorkspaceId,
+    WorkspaceLocation,
+    Option<SerializedWindowsBounds>,
+    Option<Uuid>,
+    DockStructure,
+    Option<String>,
+    Option<String>,
+    Option<i64>,
+);
+
 impl WorkspaceDb {
     /// Returns a serialized workspace for the given worktree_roots. If the passed array
     /// is empty, the most recent workspace is returned instead. If no workspace for the
@@ -307,14 +390,8 @@ impl WorkspaceDb {
 
         // Note that we re-assign the workspace_id here in case it's empty
         // and we've grabbed the most recent workspace
-        let (workspace_id, workspace_location, bounds, display, docks): (
-            WorkspaceId,
-            WorkspaceLocation,
-            Option<SerializedWindowsBounds>,
-            Option<Uuid>,
-            DockStructure,
-        ) = self
-            .select_row_bound(sql! {
+        let row = self
+            .select_row_bound::<_, WorkspaceRow>(sql! {
                 SELECT
                     workspace_id,
                     workspace_location,
@@ -327,12 +404,18 @@ impl WorkspaceDb {
                     left_dock_visible,
                     left_dock_active_panel,
                     left_dock_zoom,
+                    left_dock_size,
                     right_dock_visible,
                     right_dock_active_panel,
                     right_dock_zoom,
+                    right_dock_size,
                     bottom_dock_visible,
                     bottom_dock_active_panel,
-                    bottom_dock_zoom
+                    bottom_dock_zoom,
+                    bottom_dock_size,
+                    zoomed_pane,
+                    active_pane,
+                    last_opened
                 FROM workspaces
                 WHERE workspace_location = ?
             })
@@ -341,9 +424,25 @@ impl WorkspaceDb {
             .warn_on_err()
             .flatten()?;
 
+        self.workspace_from_row(row)
+    }
+
+    /// Completes a row of the `workspaces` table with the workspace's center group.
+    fn workspace_from_row(&self, row: WorkspaceRow) -> Option<SerializedWorkspace> {
+        let (
+            workspace_id,
+            workspace_location,
+            bounds,
+            display,
+            docks,
+            zoomed_pane,
+            active_pane,
+            last_opened,
+        ) = row;
+
         Some(SerializedWorkspace {
             id: workspace_id,
-            location: workspace_location.clone(),
+            location: workspace_location,
             center_group: self
                 .get_center_pane_group(workspace_id)
                 .context("Getting center group")
@@ -351,9 +450,28 @@ impl WorkspaceDb {
             bounds: bounds.map(|bounds| bounds.0),
             display,
             docks,
+            zoomed_pane: zoomed_pane
+                .and_then(|path| serde_json::from_str::<Vec<usize>>(&path).log_err()),
+            active_pane: active_pane
+                .and_then(|path| serde_json::from_str::<Vec<usize>>(&path).log_err()),
+            last_opened: last_opened.map(|secs| UNIX_EPOCH + Duration::from_secs(secs as u64)),
         })
     }
 
+    /// Whether a workspace is stored for exactly these worktree roots, in any order. Unlike
+    /// [`Self::workspace_for_roots`], this doesn't load the workspace's layout, so it's cheap
+    /// enough to probe with before restoring anything.
+    pub fn workspace_exists<P: AsRef<Path>>(&self, worktree_roots: &[P]) -> bool {
+        let workspace_location: WorkspaceLocation = worktree_roots.into();
+        self.select_row_bound::<_, WorkspaceId>(sql!(
+            SELECT workspace_id FROM workspaces WHERE workspace_location = ?
+        ))
+        .and_then(|mut prepared_statement| (prepared_statement)(&workspace_location))
+        .log_err()
+        .flatten()
+        .is_some()
+    }
+
     /// Saves a workspace using the worktree roots. Will garbage collect any workspaces
     /// that used this workspace previously
     pub(crate) async fn save_workspace(&self, workspace: SerializedWorkspace) {
@@ -378,29 +496,52 @@ impl WorkspaceDb {
                         left_dock_visible,
                         left_dock_active_panel,
                         left_dock_zoom,
+                        left_dock_size,
                         right_dock_visible,
                         right_dock_active_panel,
                         right_dock_zoom,
+                        right_dock_size,
                         bottom_dock_visible,
                         bottom_dock_active_panel,
                         bottom_dock_zoom,
+                        bottom_dock_size,
+                        zoomed_pane,
+                        active_pane,
                         timestamp
                     )
-                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, CURRENT_TIMESTAMP)
+                    VALUES (
+                        ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16,
+                        CURRENT_TIMESTAMP
+                    )
                     ON CONFLICT DO
                     UPDATE SET
                         workspace_location = ?2,
                         left_dock_visible = ?3,
                         left_dock_active_panel = ?4,
                         left_dock_zoom = ?5,
-                        right_dock_visible = ?6,
-                        right_dock_active_panel = ?7,
-                        right_dock_zoom = ?8,
-                        bottom_dock_visible = ?9,
-                        bottom_dock_active_panel = ?10,
-                        bottom_dock_zoom = ?11,
+                        left_dock_size = ?6,
+                        right_dock_visible = ?7,
+                        right_dock_active_panel = ?8,
+                        right_dock_zoom = ?9,
+                        right_dock_size = ?10,
+                        bottom_dock_visible = ?11,
+                        bottom_dock_active_panel = ?12,
+                        bottom_dock_zoom = ?13,
+                        bottom_dock_size = ?14,
+                        zoomed_pane = ?15,
+                        active_pane = ?16,
                         timestamp = CURRENT_TIMESTAMP
-                ))?((workspace.id, &workspace.location, workspace.docks))
+                ))?((
+                    workspace.id,
+                    &workspace.location,
+                    workspace.docks,
+                    workspace
+                        .zoomed_pane
+                        .map(|path| serde_json::json!(path).to_string()),
+                    workspace
+                        .active_pane
+                        .map(|path| serde_json::json!(path).to_string()),
+                ))
                 .context("Updating workspace")?;
 
                 // Save center pane group
@@ -414,6 +555,26 @@ impl WorkspaceDb {
         .await;
     }
 
+    query! {
+        pub fn get_docks(workspace_id: WorkspaceId) -> Result<Option<DockStructure>> {
+            SELECT
+                left_dock_visible,
+                left_dock_active_panel,
+                left_dock_zoom,
+                left_dock_size,
+                right_dock_visible,
+                right_dock_active_panel,
+                right_dock_zoom,
+                right_dock_size,
+                bottom_dock_visible,
+                bottom_dock_active_panel,
+                bottom_dock_zoom,
+                bottom_dock_size
+            FROM workspaces
+            WHERE workspace_id = ?
+        }
+    }
+
     query! {
         pub async fn next_id() -> Result<WorkspaceId> {
             INSERT INTO workspaces DEFAULT VALUES RETURNING workspace_id
@@ -429,13 +590,86 @@ impl WorkspaceDb {
         }
     }
 
+    // Panes and pane groups are removed along with the workspace through their foreign keys.
+    // Deleting a workspace that doesn't exist is a no-op.
     query! {
-        async fn delete_stale_workspace(id: WorkspaceId) -> Result<()> {
+        pub async fn delete_workspace(id: WorkspaceId) -> Result<()> {
             DELETE FROM workspaces
             WHERE workspace_id IS ?
         }
     }
 
+    /// Moves a workspace to `new_roots`, e.g. after its project folders were moved on disk.
+    /// Its layout is kept, as it's stored against the workspace's id rather than its roots.
+    /// Fails if another workspace is already stored for `new_roots`.
+    pub async fn update_workspace_roots<P: AsRef<Path>>(
+        &self,
+        workspace_id: WorkspaceId,
+        new_roots: &[P],
+    ) -> Result<WorkspaceId> {
+        let location: WorkspaceLocation = new_roots.into();
+        self.write(move |conn| {
+            conn.with_savepoint("update_workspace_roots", || {
+                conn.select_row_bound::<_, WorkspaceId>(sql!(
+                    SELECT workspace_id FROM workspaces WHERE workspace_id = ?
+                ))?(workspace_id)?
+                .ok_or_else(|| anyhow!("Workspace {workspace_id} not found"))?;
+
+                let existing = conn.select_row_bound::<_, WorkspaceId>(sql!(
+                    SELECT workspace_id FROM workspaces
+                    WHERE workspace_location = ? AND workspace_id != ?
+                ))?((&location, workspace_id))?;
+                if let Some(existing) = existing {
+                    bail!("Workspace {existing} is already stored for {location:?}");
+                }
+
+                conn.exec_bound(sql!(
+                    UPDATE workspaces SET workspace_location = ?2 WHERE workspace_id = ?1
+                ))?((workspace_id, &location))?;
+                Ok(workspace_id)
+            })
+        })
+        .await
+    }
+
+    /// Returns every stored workspace, most recently opened first. Workspaces that were
+    /// never opened come last, most recently used first.
+    pub fn all_workspaces(&self) -> Result<Vec<SerializedWorkspace>> {
+        let rows = self.select::<WorkspaceRow>(sql!(
+            SELECT
+                workspace_id,
+                workspace_location,
+                window_state,
+                window_x,
+                window_y,
+                window_width,
+                window_height,
+                display,
+                left_dock_visible,
+                left_dock_active_panel,
+                left_dock_zoom,
+                left_dock_size,
+                right_dock_visible,
+                right_dock_active_panel,
+                right_dock_zoom,
+                right_dock_size,
+                bottom_dock_visible,
+                bottom_dock_active_panel,
+                bottom_dock_zoom,
+                bottom_dock_size,
+                zoomed_pane,
+                active_pane,
+                last_opened
+            FROM workspaces
+            WHERE workspace_location IS NOT NULL
+            ORDER BY last_opened IS NULL, last_opened DESC, timestamp DESC
+        ))?()?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| self.workspace_from_row(row))
+            .collect())
+    }
+
     // Returns the recent locations which are still valid on disk and deletes ones which no longer
     // exist.
     pub async fn recent_workspaces_on_disk(&self) -> Result<Vec<(WorkspaceId, WorkspaceLocation)>> {
@@ -447,7 +681,7 @@ impl WorkspaceDb {
             {
                 result.push((id, location));
             } else {
-                delete_tasks.push(self.delete_stale_workspace(id));
+                delete_tasks.push(self.delete_workspace(id));
             }
         }
 
@@ -464,6 +698,39 @@ impl WorkspaceDb {
             .map(|(_, location)| location))
     }
 
+    /// Dumps every stored workspace as JSON, for inspecting or backing up layout state.
+    pub fn export_to_json(&self) -> Result<serde_json::Value> {
+        let workspaces = serde_json::to_value(self.all_workspaces()?)?;
+        Ok(serde_json::json!({ "workspaces": workspaces }))
+    }
+
+    /// Stores the workspaces from a document produced by [`Self::export_to_json`],
+    /// replacing any stored workspaces with the same ids or locations.
+    pub async fn import_from_json(&self, json: &serde_json::Value) -> Result<()> {
+        let workspaces: Vec<SerializedWorkspace> = serde_json::from_value(
+            json.get("workspaces")
+                .context("Missing workspaces array")?
+                .clone(),
+        )?;
+        for workspace in workspaces {
+            let (id, bounds, display, last_opened) = (
+                workspace.id,
+                workspace.bounds,
+                workspace.display,
+                workspace.last_opened,
+            );
+            self.save_workspace(workspace).await;
+            // Saving a workspace leaves out what's recorded when its window is used
+            if let (Some(bounds), Some(display)) = (bounds, display) {
+                self.save_window_bounds(id, bounds, display).await?;
+            }
+            if let Some(last_opened) = last_opened {
+                self.record_opened(id, last_opened).await?;
+            }
+        }
+        Ok(())
+    }
+
     fn get_center_pane_group(&self, workspace_id: WorkspaceId) -> Result<SerializedPaneGroup> {
         Ok(self
             .get_pane_group(workspace_id, None)?
@@ -473,6 +740,7 @@ impl WorkspaceDb {
                 SerializedPaneGroup::Pane(SerializedPane {
                     active: true,
                     children: vec![],
+                    locked: false,
                 })
             }))
     }
@@ -489,9 +757,10 @@ impl WorkspaceDb {
             Option<PaneId>,
             Option<bool>,
             Option<String>,
+            Option<bool>,
         );
         self.select_bound::<GroupKey, GroupOrPane>(sql!(
-            SELECT group_id, axis, pane_id, active, flexes
+            SELECT group_id, axis, pane_id, active, flexes, locked
                 FROM (SELECT
                         group_id,
                         axis,
@@ -500,7 +769,8 @@ impl WorkspaceDb {
                         position,
                         parent_group_id,
                         workspace_id,
-                        flexes
+                        flexes,
+                        NULL as locked
                       FROM pane_groups
                     UNION
                       SELECT
@@ -511,14 +781,15 @@ impl WorkspaceDb {
                         position,
                         parent_group_id,
                         panes.workspace_id as workspace_id,
-                        NULL
+                        NULL,
+                        panes.locked as locked
                       FROM center_panes
                       JOIN panes ON center_panes.pane_id = panes.pane_id)
                 WHERE parent_group_id IS ? AND workspace_id = ?
                 ORDER BY position
         ))?((group_id, workspace_id))?
         .into_iter()
-        .map(|(group_id, axis, pane_id, active, flexes)| {
+        .map(|(group_id, axis, pane_id, active, flexes, locked)| {
             if let Some((group_id, axis)) = group_id.zip(axis) {
                 let flexes = flexes
                     .map(|flexes: String| serde_json::from_str::<Vec<f32>>(&flexes))
@@ -530,24 +801,36 @@ impl WorkspaceDb {
                     flexes,
                 })
             } else if let Some((pane_id, active)) = pane_id.zip(active) {
-                Ok(SerializedPaneGroup::Pane(SerializedPane::new(
-                    self.get_items(pane_id)?,
-                    active,
-                )))
+                Ok(SerializedPaneGroup::Pane(
+                    SerializedPane::new(self.get_items(pane_id)?, active)
+                        .with_locked(locked.unwrap_or(false)),
+                ))
             } else {
                 bail!("Pane Group Child was neither a pane group or a pane");
             }
         })
-        // Filter out panes and pane groups which don't have any children or items
+        // Filter out panes and pane groups which don't have any children or items, unless
+        // the pane is locked
         .filter(|pane_group| match pane_group {
             Ok(SerializedPaneGroup::Group { children, .. }) => !children.is_empty(),
-            Ok(SerializedPaneGroup::Pane(pane)) => !pane.children.is_empty(),
+            Ok(SerializedPaneGroup::Pane(pane)) => pane.locked || !pane.children.is_empty(),
             _ => true,
         })
         .collect::<Result<_>>()
     }
 
-    fn save_pane_group(
+    /// Runs `f` on the write connection inside a savepoint, so that the writes it makes are
+    /// either all kept or, when it returns an error, all rolled back. The `save_*` helpers
+    /// that take a [`Connection`] can be used from within `f` to group several saves.
+    pub async fn transaction<T: 'static + Send + Sync>(
+        &self,
+        f: impl 'static + Send + FnOnce(&Connection) -> Result<T>,
+    ) -> Result<T> {
+        self.write(move |conn| conn.with_savepoint("transaction", || f(conn)))
+            .await
+    }
+
+    pub fn save_pane_group(
         conn: &Connection,
         workspace_id: WorkspaceId,
         pane_group: &SerializedPaneGroup,
@@ -597,17 +880,17 @@ impl WorkspaceDb {
         }
     }
 
-    fn save_pane(
+    pub fn save_pane(
         conn: &Connection,
         workspace_id: WorkspaceId,
         pane: &SerializedPane,
         parent: Option<(GroupId, usize)>,
     ) -> Result<PaneId> {
         let pane_id = conn.select_row_bound::<_, i64>(sql!(
-            INSERT INTO panes(workspace_id, active)
-            VALUES (?, ?)
+            INSERT INTO panes(workspace_id, active, locked)
+            VALUES (?, ?, ?)
             RETURNING pane_id
-        ))?((workspace_id, pane.active))?
+        ))?((workspace_id, pane.active, pane.locked))?
         .ok_or_else(|| anyhow!("Could not retrieve inserted pane_id"))?;
 
         let (parent_id, order) = unzip_option(parent);
@@ -629,7 +912,7 @@ impl WorkspaceDb {
         ))?(pane_id)?)
     }
 
-    fn save_items(
+    pub fn save_items(
         conn: &Connection,
         workspace_id: WorkspaceId,
         pane_id: PaneId,
@@ -645,6 +928,70 @@ impl WorkspaceDb {
         Ok(())
     }
 
+    query! {
+        async fn set_last_opened(workspace_id: WorkspaceId, last_opened: i64) -> Result<()> {
+            UPDATE workspaces
+            SET last_opened = ?2
+            WHERE workspace_id = ?1
+        }
+    }
+
+    /// Records that the workspace was opened at `opened_at`, which orders [`Self::all_workspaces`].
+    pub async fn record_opened(
+        &self,
+        workspace_id: WorkspaceId,
+        opened_at: SystemTime,
+    ) -> Result<()> {
+        let secs = opened_at.duration_since(UNIX_EPOCH)?.as_secs();
+        self.set_last_opened(workspace_id, secs as i64).await
+    }
+
+    /// Summarizes the workspace's saved center layout from its stored rows, without loading
+    /// the tree or any of its items. Returns `None` if no layout is saved for it.
+    pub fn layout_stats(&self, workspace_id: WorkspaceId) -> Option<LayoutStats> {
+        let (pane_count, max_depth, axis_count) = self
+            .select_row_bound::<WorkspaceId, (usize, usize, usize)>(sql!(
+                WITH RECURSIVE axes(group_id, depth) AS (
+                    SELECT group_id, 1
+                    FROM pane_groups
+                    WHERE workspace_id = ?1 AND parent_group_id IS NULL
+                    UNION ALL
+                    SELECT pane_groups.group_id, axes.depth + 1
+                    FROM pane_groups
+                    JOIN axes ON pane_groups.parent_group_id = axes.group_id
+                )
+                SELECT
+                    (SELECT COUNT(*)
+                        FROM center_panes
+                        JOIN panes ON center_panes.pane_id = panes.pane_id
+                        WHERE panes.workspace_id = ?1),
+                    (SELECT COALESCE(MAX(depth), 0) FROM axes),
+                    (SELECT COUNT(*) FROM axes)
+            ))
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id))
+            .context("Counting layout stats")
+            .log_err()
+            .flatten()?;
+
+        (pane_count > 0).then_some(LayoutStats {
+            pane_count,
+            max_depth,
+            axis_count,
+        })
+    }
+
+    /// Rebuilds the database file, reclaiming the space left by deleted workspaces.
+    pub async fn vacuum(&self) -> Result<()> {
+        self.write(|conn| conn.exec("VACUUM")?().context("Vacuuming workspace db"))
+            .await
+    }
+
+    /// Whether SQLite's integrity check finds the database intact.
+    pub fn check_integrity(&self) -> Result<bool> {
+        let result = self.select_row::<String>("PRAGMA integrity_check")?()?;
+        Ok(result.as_deref() == Some("ok"))
+    }
+
     query! {
         pub async fn update_timestamp(workspace_id: WorkspaceId) -> Result<()> {
             UPDATE workspaces
@@ -653,8 +1000,44 @@ impl WorkspaceDb {
         }
     }
 
+    /// Stores the geometry of the workspace's window. Fixed bounds are stored relative to the
+    /// origin of `display`, so that they can be placed on another display if it's gone.
+    pub async fn save_window_bounds(
+        &self,
+        workspace_id: WorkspaceId,
+        bounds: WindowBounds,
+        display: Uuid,
+    ) -> Result<()> {
+        self.set_window_bounds(workspace_id, SerializedWindowsBounds(bounds), display)
+            .await
+    }
+
+    /// The window geometry stored for the workspace by [`Self::save_window_bounds`], and the
+    /// display it was on. Fixed bounds are relative to that display, which may no longer be
+    /// connected, so callers should place and clamp them within whichever display they use.
+    pub fn get_window_bounds(
+        &self,
+        workspace_id: WorkspaceId,
+    ) -> Result<Option<(WindowBounds, Option<Uuid>)>> {
+        let row = self
+            .select_row_bound::<WorkspaceId, (Option<SerializedWindowsBounds>, Option<Uuid>)>(
+                sql!(
+                    SELECT
+                        window_state,
+                        window_x,
+                        window_y,
+                        window_width,
+                        window_height,
+                        display
+                    FROM workspaces
+                    WHERE workspace_id = ?
+                ),
+            )?(workspace_id)?;
+        Ok(row.and_then(|(bounds, display)| Some((bounds?.0, display))))
+    }
+
     query! {
-        pub(crate) async fn set_window_bounds(workspace_id: WorkspaceId, bounds: SerializedWindowsBounds, display: Uuid) -> Result<()> {
+        async fn set_window_bounds(workspace_id: WorkspaceId, bounds: SerializedWindowsBounds, display: Uuid) -> Result<()> {
             UPDATE workspaces
             SET window_state = ?2,
                 window_x = ?3,
@@ -670,8 +1053,9 @@ impl WorkspaceDb {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use db::open_test_db;
+    use db::{open_file_db, open_test_db};
     use gpui;
+    use sqlez::domain::Domain;
 
     #[gpui::test]
     async fn test_next_id_stability() {
@@ -750,6 +1134,9 @@ mod tests {
             bounds: Default::default(),
             display: Default::default(),
             docks: Default::default(),
+            zoomed_pane: None,
+            active_pane: None,
+            last_opened: None,
         };
 
         let workspace_2 = SerializedWorkspace {
@@ -759,6 +1146,9 @@ mod tests {
             bounds: Default::default(),
             display: Default::default(),
             docks: Default::default(),
+            zoomed_pane: None,
+            active_pane: None,
+            last_opened: None,
         };
 
         db.save_workspace(workspace_1.clone()).await;
@@ -857,6 +1247,9 @@ mod tests {
             bounds: Default::default(),
             display: Default::default(),
             docks: Default::default(),
+            zoomed_pane: None,
+            active_pane: None,
+            last_opened: None,
         };
 
         db.save_workspace(workspace.clone()).await;
@@ -885,6 +1278,9 @@ mod tests {
             bounds: Default::default(),
             display: Default::default(),
             docks: Default::default(),
+            zoomed_pane: None,
+            active_pane: None,
+            last_opened: None,
         };
 
         let mut workspace_2 = SerializedWorkspace {
@@ -894,6 +1290,9 @@ mod tests {
             bounds: Default::default(),
             display: Default::default(),
             docks: Default::default(),
+            zoomed_pane: None,
+            active_pane: None,
+            last_opened: None,
         };
 
         db.save_workspace(workspace_1.clone()).await;
@@ -930,6 +1329,9 @@ mod tests {
             bounds: Default::default(),
             display: Default::default(),
             docks: Default::default(),
+            zoomed_pane: None,
+            active_pane: None,
+            last_opened: None,
         };
 
         db.save_workspace(workspace_3.clone()).await;
@@ -949,7 +1351,7 @@ mod tests {
         );
     }
 
-    use crate::persistence::model::SerializedWorkspace;
+    use crate::persistence::model::{DockData, SerializedWorkspace};
     use crate::persistence::model::{SerializedItem, SerializedPane, SerializedPaneGroup};
 
     fn default_workspace<P: AsRef<Path>>(
@@ -963,6 +1365,9 @@ mod tests {
             bounds: Default::default(),
             display: Default::default(),
             docks: Default::default(),
+            zoomed_pane: None,
+            active_pane: None,
+            last_opened: None,
         }
     }
 
@@ -1018,6 +1423,764 @@ mod tests {
         assert_eq!(workspace.center_group, new_workspace.center_group);
     }
 
+    #[gpui::test]
+    async fn test_layout_stats() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_layout_stats").await);
+        let pane = || SerializedPaneGroup::Pane(SerializedPane::new(vec![], false));
+
+        //  ---------------------
+        //  |   | 2 |   |       |
+        //  | 1 |---| 4 |       |
+        //  |   | 3 |   |   6   |
+        //  |-----------|       |
+        //  |     5     |       |
+        //  ---------------------
+        let center_group = group(
+            Axis::Horizontal,
+            vec![
+                group(
+                    Axis::Vertical,
+                    vec![
+                        group(
+                            Axis::Horizontal,
+                            vec![pane(), group(Axis::Vertical, vec![pane(), pane()]), pane()],
+                        ),
+                        pane(),
+                    ],
+                ),
+                pane(),
+            ],
+        );
+        let workspace = default_workspace(&["/tmp"], &center_group);
+        db.save_workspace(workspace.clone()).await;
+        assert_eq!(
+            db.layout_stats(workspace.id),
+            Some(LayoutStats {
+                pane_count: 6,
+                max_depth: 4,
+                axis_count: 4,
+            })
+        );
+
+        let lone_pane = SerializedWorkspace {
+            id: workspace.id + 1,
+            ..default_workspace(&["/lone"], &pane())
+        };
+        db.save_workspace(lone_pane.clone()).await;
+        assert_eq!(
+            db.layout_stats(lone_pane.id),
+            Some(LayoutStats {
+                pane_count: 1,
+                max_depth: 0,
+                axis_count: 0,
+            })
+        );
+
+        assert_eq!(db.layout_stats(workspace.id + 2), None);
+    }
+
+    #[gpui::test]
+    async fn test_transaction() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_transaction").await);
+        let pane = || SerializedPaneGroup::Pane(SerializedPane::new(vec![], false));
+        let workspace = default_workspace(&["/tmp"], &pane());
+        db.save_workspace(workspace.clone()).await;
+
+        let replace_center = |fail: bool| {
+            let workspace_id = workspace.id;
+            let center_group = group(Axis::Horizontal, vec![pane(), pane()]);
+            move |conn: &Connection| {
+                conn.exec_bound(sql!(
+                    DELETE FROM pane_groups WHERE workspace_id = ?1;
+                    DELETE FROM panes WHERE workspace_id = ?1;))?(workspace_id)?;
+                WorkspaceDb::save_pane_group(conn, workspace_id, &center_group, None)?;
+                if fail {
+                    bail!("failed after saving the pane group");
+                }
+                Ok(())
+            }
+        };
+
+        // An error partway through leaves the previous layout in place.
+        assert!(db.transaction(replace_center(true)).await.is_err());
+        assert_eq!(db.layout_stats(workspace.id).unwrap().pane_count, 1);
+
+        db.transaction(replace_center(false)).await.unwrap();
+        assert_eq!(db.layout_stats(workspace.id).unwrap().pane_count, 2);
+    }
+
+    #[gpui::test]
+    async fn test_nested_pane_group_serialization() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_nested_pane_group_serialization").await);
+
+        //  -----------------
+        //  | 1     |       |
+        //  | - - - |   4   |
+        //  | 2 | 3 |       |
+        //  -----------------
+        let pane = |item_id, active| {
+            SerializedPaneGroup::Pane(SerializedPane::new(
+                vec![SerializedItem::new("Terminal", item_id, true)],
+                active,
+            ))
+        };
+        let center_pane = group(
+            Axis::Horizontal,
+            vec![
+                group(
+                    Axis::Vertical,
+                    vec![
+                        pane(1, false),
+                        group(Axis::Horizontal, vec![pane(2, false), pane(3, true)]),
+                    ],
+                ),
+                pane(4, false),
+            ],
+        );
+
+        let workspace = default_workspace(&["/tmp"], &center_pane);
+        db.save_workspace(workspace.clone()).await;
+
+        let new_workspace = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(workspace.center_group, new_workspace.center_group);
+    }
+
+    #[gpui::test]
+    async fn test_pane_group_flexes() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_pane_group_flexes").await);
+
+        //  ---------------------
+        //  | 1     | 3   | 4   |
+        //  | - - - |     |     |
+        //  | 2     |     |     |
+        //  ---------------------
+        let center_pane = SerializedPaneGroup::Group {
+            axis: SerializedAxis(Axis::Horizontal),
+            flexes: Some(vec![1.5, 0.9, 0.6]),
+            children: vec![
+                SerializedPaneGroup::Group {
+                    axis: SerializedAxis(Axis::Vertical),
+                    flexes: Some(vec![0.25, 1.75]),
+                    children: vec![
+                        SerializedPaneGroup::Pane(SerializedPane::new(
+                            vec![SerializedItem::new("Terminal", 1, true)],
+                            true,
+                        )),
+                        SerializedPaneGroup::Pane(SerializedPane::new(
+                            vec![SerializedItem::new("Terminal", 2, true)],
+                            false,
+                        )),
+                    ],
+                },
+                SerializedPaneGroup::Pane(SerializedPane::new(
+                    vec![SerializedItem::new("Terminal", 3, true)],
+                    false,
+                )),
+                SerializedPaneGroup::Pane(SerializedPane::new(
+                    vec![SerializedItem::new("Terminal", 4, true)],
+                    false,
+                )),
+            ],
+        };
+
+        let workspace = default_workspace(&["/tmp"], &center_pane);
+        db.save_workspace(workspace.clone()).await;
+
+        let new_workspace = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(workspace.center_group, new_workspace.center_group);
+    }
+
+    #[gpui::test]
+    async fn test_all_workspaces_by_last_opened() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_all_workspaces_by_last_opened").await);
+
+        for (id, root) in [(1, "/tmp1"), (2, "/tmp2"), (3, "/tmp3")] {
+            let mut workspace = default_workspace(&[root], &Default::default());
+            workspace.id = id;
+            db.save_workspace(workspace).await;
+        }
+
+        let start = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        db.record_opened(2, start).await.unwrap();
+        db.record_opened(1, start + Duration::from_secs(10))
+            .await
+            .unwrap();
+
+        let workspaces = db.all_workspaces().unwrap();
+        assert_eq!(
+            workspaces
+                .iter()
+                .map(|workspace| (workspace.id, workspace.last_opened))
+                .collect::<Vec<_>>(),
+            [
+                (1, Some(start + Duration::from_secs(10))),
+                (2, Some(start)),
+                (3, None)
+            ]
+        );
+
+        db.record_opened(2, start + Duration::from_secs(20))
+            .await
+            .unwrap();
+        let ids = db
+            .all_workspaces()
+            .unwrap()
+            .into_iter()
+            .map(|workspace| workspace.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [2, 1, 3]);
+    }
+
+    #[gpui::test]
+    async fn test_update_workspace_roots() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_update_workspace_roots").await);
+
+        let center_group = group(
+            Axis::Horizontal,
+            vec![
+                SerializedPaneGroup::Pane(SerializedPane::new(
+                    vec![SerializedItem::new("Terminal", 5, true)],
+                    true,
+                )),
+                SerializedPaneGroup::Pane(SerializedPane::new(
+                    vec![SerializedItem::new("Terminal", 6, true)],
+                    false,
+                )),
+            ],
+        );
+        let mut workspace = default_workspace(&["/old", "/tmp"], &center_group);
+        workspace.docks.left = DockData {
+            visible: true,
+            active_panel: Some("ProjectPanel".to_string()),
+            zoom: false,
+            size: Some(240.),
+        };
+        db.save_workspace(workspace.clone()).await;
+
+        let other = SerializedWorkspace {
+            id: workspace.id + 1,
+            ..default_workspace(&["/other"], &Default::default())
+        };
+        db.save_workspace(other.clone()).await;
+
+        assert_eq!(
+            db.update_workspace_roots(workspace.id, &["/new", "/tmp"])
+                .await
+                .unwrap(),
+            workspace.id
+        );
+        assert_eq!(db.workspace_for_roots(&["/old", "/tmp"]), None);
+        workspace.location = (["/new", "/tmp"]).into();
+        assert_eq!(
+            db.workspace_for_roots(&["/tmp", "/new"]).unwrap(),
+            workspace
+        );
+        assert_eq!(db.get_docks(workspace.id).unwrap(), Some(workspace.docks));
+
+        // Moving onto another workspace's roots, or moving a missing workspace, fails.
+        assert!(db
+            .update_workspace_roots(workspace.id, &["/other"])
+            .await
+            .is_err());
+        assert!(db
+            .update_workspace_roots(other.id + 1, &["/elsewhere"])
+            .await
+            .is_err());
+        assert_eq!(db.workspace_for_roots(&["/other"]).unwrap(), other);
+        assert_eq!(db.workspace_for_roots(&["/elsewhere"]), None);
+    }
+
+    #[gpui::test]
+    async fn test_vacuum() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_vacuum").await);
+        for id in 1..=4 {
+            let mut workspace = default_workspace(&[&format!("/tmp{id}")], &Default::default());
+            workspace.id = id;
+            db.save_workspace(workspace).await;
+        }
+        for id in 1..=3 {
+            db.delete_workspace(id).await.unwrap();
+        }
+
+        db.vacuum().await.unwrap();
+        assert!(db.check_integrity().unwrap());
+        let remaining = db.all_workspaces().unwrap();
+        assert_eq!(
+            remaining
+                .iter()
+                .map(|workspace| workspace.id)
+                .collect::<Vec<_>>(),
+            [4]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_delete_workspace() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_delete_workspace").await);
+
+        let mut workspaces = Vec::new();
+        for (id, root) in [(1, "/tmp1"), (2, "/tmp2"), (3, "/tmp3")] {
+            let mut workspace = default_workspace(&[root], &Default::default());
+            workspace.id = id;
+            db.save_workspace(workspace.clone()).await;
+            workspaces.push(workspace);
+        }
+
+        db.delete_workspace(2).await.unwrap();
+        // Deleting a workspace that is already gone is a no-op.
+        db.delete_workspace(2).await.unwrap();
+
+        let mut remaining = db.all_workspaces().unwrap();
+        remaining.sort_by_key(|workspace| workspace.id);
+        assert_eq!(
+            remaining,
+            vec![workspaces[0].clone(), workspaces[2].clone()]
+        );
+        assert_eq!(db.workspace_for_roots(&["/tmp2"]), None);
+    }
+
+    #[gpui::test]
+    async fn test_window_bounds_roundtrip() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_window_bounds_roundtrip").await);
+
+        let mut workspace = default_workspace(&["/tmp"], &Default::default());
+        workspace.id = db.next_id().await.unwrap();
+        db.save_workspace(workspace.clone()).await;
+        assert_eq!(db.get_window_bounds(workspace.id).unwrap(), None);
+        assert_eq!(db.get_window_bounds(workspace.id + 1).unwrap(), None);
+
+        let display = Uuid::new_v4();
+        let bounds = WindowBounds::Fixed(Bounds {
+            origin: point(10f64.into(), 20f64.into()),
+            size: size(800f64.into(), 600f64.into()),
+        });
+        db.save_window_bounds(workspace.id, bounds, display)
+            .await
+            .unwrap();
+        assert_eq!(
+            db.get_window_bounds(workspace.id).unwrap(),
+            Some((bounds, Some(display)))
+        );
+
+        db.save_window_bounds(workspace.id, WindowBounds::Maximized, display)
+            .await
+            .unwrap();
+        assert_eq!(
+            db.get_window_bounds(workspace.id).unwrap(),
+            Some((WindowBounds::Maximized, Some(display)))
+        );
+        assert_eq!(
+            db.workspace_for_roots(&["/tmp"]).unwrap().bounds,
+            Some(WindowBounds::Maximized)
+        );
+    }
+
+    #[gpui::test]
+    async fn test_workspace_exists() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_workspace_exists").await);
+
+        assert!(!db.workspace_exists(&["/tmp", "/tmp2"]));
+        // Looking a workspace up doesn't store one.
+        assert_eq!(db.workspace_for_roots(&["/tmp", "/tmp2"]), None);
+        assert!(!db.workspace_exists(&["/tmp", "/tmp2"]));
+
+        let mut workspace = default_workspace(&["/tmp", "/tmp2"], &Default::default());
+        workspace.id = db.next_id().await.unwrap();
+        db.save_workspace(workspace).await;
+        assert!(db.workspace_exists(&["/tmp", "/tmp2"]));
+        assert!(db.workspace_exists(&["/tmp2", "/tmp"]));
+        assert!(!db.workspace_exists(&["/tmp"]));
+    }
+
+    #[gpui::test]
+    async fn test_migrate_from_legacy_layout() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_migrate_from_legacy_layout").await);
+
+        let mut current = default_workspace(
+            &["/current"],
+            &SerializedPaneGroup::Pane(SerializedPane::new(
+                vec![SerializedItem::new("Terminal", 1, true)],
+                true,
+            )),
+        );
+        current.id = db.next_id().await.unwrap();
+        db.save_workspace(current.clone()).await;
+
+        // A workspace from before the center pane group was stored, with only a dock pane.
+        let legacy_id = db.next_id().await.unwrap();
+        let location: WorkspaceLocation = (&["/legacy"]).into();
+        db.write(move |conn| {
+            conn.exec_bound(sql!(
+                UPDATE workspaces SET workspace_location = ? WHERE workspace_id = ?
+            ))?((location, legacy_id))?;
+            let pane_id = conn.select_row_bound::<_, PaneId>(sql!(
+                INSERT INTO panes(workspace_id, active) VALUES (?, 1) RETURNING pane_id
+            ))?(legacy_id)?
+            .context("No pane inserted")?;
+            conn.exec_bound(sql!(
+                UPDATE workspaces SET dock_pane = ? WHERE workspace_id = ?
+            ))?((pane_id, legacy_id))?;
+            WorkspaceDb::save_items(
+                conn,
+                legacy_id,
+                pane_id,
+                &[
+                    SerializedItem::new("Terminal", 2, false),
+                    SerializedItem::new("Terminal", 3, true),
+                ],
+            )
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            db.workspace_for_roots(&["/legacy"]).unwrap().center_group,
+            SerializedPaneGroup::Pane(SerializedPane::new(vec![], true))
+        );
+
+        // Run the import again, as it would for a database last used by an early version.
+        assert!(WorkspaceDb::migrations().contains(&LEGACY_LAYOUT_MIGRATION));
+        db.write(|conn| conn.exec(LEGACY_LAYOUT_MIGRATION)?())
+            .await
+            .unwrap();
+        assert_eq!(
+            db.workspace_for_roots(&["/legacy"]).unwrap().center_group,
+            SerializedPaneGroup::Pane(SerializedPane::new(
+                vec![
+                    SerializedItem::new("Terminal", 2, false),
+                    SerializedItem::new("Terminal", 3, true),
+                ],
+                true,
+            ))
+        );
+        assert_eq!(db.workspace_for_roots(&["/current"]).unwrap(), current);
+    }
+
+    #[gpui::test]
+    async fn test_json_roundtrip() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_json_roundtrip").await);
+
+        let mut workspace_1 = default_workspace(
+            &["/tmp", "/tmp2"],
+            &SerializedPaneGroup::Group {
+                axis: SerializedAxis(Axis::Horizontal),
+                flexes: Some(vec![0.5, 1.5]),
+                children: vec![
+                    SerializedPaneGroup::Pane(SerializedPane::new(
+                        vec![
+                            SerializedItem::new("Terminal", 1, false),
+                            SerializedItem::new("Terminal", 2, true),
+                        ],
+                        true,
+                    )),
+                    group(Axis::Vertical, vec![Default::default(), Default::default()]),
+                ],
+            },
+        );
+        workspace_1.id = 1;
+        workspace_1.docks.bottom = DockData {
+            visible: true,
+            active_panel: Some("TerminalPanel".to_string()),
+            zoom: false,
+            size: None,
+        };
+        workspace_1.zoomed_pane = Some(vec![1, 0]);
+        workspace_1.active_pane = Some(vec![0]);
+        let mut workspace_2 = default_workspace(&["/tmp3"], &Default::default());
+        workspace_2.id = 2;
+        db.save_workspace(workspace_1).await;
+        db.save_workspace(workspace_2).await;
+
+        // What's recorded as the window is used is exported along with the layout
+        let bounds = WindowBounds::Fixed(Bounds {
+            origin: point(10f64.into(), 20f64.into()),
+            size: size(800f64.into(), 600f64.into()),
+        });
+        let display = Uuid::new_v4();
+        db.save_window_bounds(1, bounds, display).await.unwrap();
+        let last_opened = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        db.record_opened(1, last_opened).await.unwrap();
+
+        let json = db.export_to_json().unwrap();
+
+        let imported_db = WorkspaceDb(open_test_db("test_json_roundtrip_import").await);
+        imported_db.import_from_json(&json).await.unwrap();
+
+        let sorted_workspaces = |db: &WorkspaceDb| {
+            let mut workspaces = db.all_workspaces().unwrap();
+            workspaces.sort_by_key(|workspace| workspace.id);
+            workspaces
+        };
+        assert_eq!(sorted_workspaces(&imported_db), sorted_workspaces(&db));
+        assert_eq!(sorted_workspaces(&imported_db).len(), 2);
+        let imported = imported_db.workspace_for_roots(&["/tmp", "/tmp2"]).unwrap();
+        assert_eq!(imported.bounds, Some(bounds));
+        assert_eq!(imported.display, Some(display));
+        assert_eq!(imported.last_opened, Some(last_opened));
+        assert_eq!(imported.zoomed_pane, Some(vec![1, 0]));
+        assert_eq!(imported.active_pane, Some(vec![0]));
+
+        // Malformed optional fields are reported rather than dropped
+        let mut json = json;
+        json["workspaces"][0]["zoomed_pane"] = serde_json::json!("not a path");
+        assert!(imported_db.import_from_json(&json).await.is_err());
+    }
+
+    #[gpui::test]
+    async fn test_workspace_location_normalization() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_workspace_location_normalization").await);
+
+        let workspace = default_workspace(&["/tmp", "/tmp2"], &Default::default());
+        db.save_workspace(workspace.clone()).await;
+
+        for roots in [
+            &["/tmp2", "/tmp"][..],
+            &["/tmp", "/tmp2", "/tmp"],
+            &["/tmp2/", "/tmp/./"],
+        ] {
+            assert_eq!(
+                db.workspace_for_roots(roots).map(|workspace| workspace.id),
+                Some(workspace.id),
+                "roots: {roots:?}"
+            );
+        }
+    }
+
+    #[gpui::test]
+    async fn test_workspace_location_canonicalization() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_workspace_location_canonicalization").await);
+
+        let tempdir = tempfile::Builder::new()
+            .prefix("WorkspaceDb")
+            .tempdir()
+            .unwrap();
+        let root = tempdir.path().join("root");
+        std::fs::create_dir(&root).unwrap();
+        let link = tempdir.path().join("link");
+        std::os::unix::fs::symlink(&root, &link).unwrap();
+
+        let workspace = default_workspace(&[&root], &Default::default());
+        db.save_workspace(workspace.clone()).await;
+
+        for alias in [link.clone(), root.join("..").join("root"), link.join(".")] {
+            assert_eq!(
+                db.workspace_for_roots(&[&alias])
+                    .map(|workspace| workspace.id),
+                Some(workspace.id),
+                "alias: {alias:?}"
+            );
+        }
+    }
+
+    #[gpui::test]
+    async fn test_zoomed_pane_serialization() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_zoomed_pane_serialization").await);
+
+        let mut workspace = default_workspace(&["/tmp"], &Default::default());
+        for zoomed_pane in [Some(vec![1, 0, 2]), Some(vec![]), None] {
+            workspace.zoomed_pane = zoomed_pane;
+            db.save_workspace(workspace.clone()).await;
+            assert_eq!(db.workspace_for_roots(&["/tmp"]).unwrap(), workspace);
+        }
+    }
+
+    #[gpui::test]
+    async fn test_active_pane_serialization() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_active_pane_serialization").await);
+
+        let center_group = group(
+            Axis::Horizontal,
+            vec![
+                SerializedPaneGroup::Pane(SerializedPane::new(vec![], false)),
+                SerializedPaneGroup::Pane(SerializedPane::new(vec![], false)),
+            ],
+        );
+        let mut workspace = default_workspace(&["/tmp"], &center_group);
+        workspace.active_pane = Some(vec![1]);
+        db.save_workspace(workspace.clone()).await;
+        assert_eq!(db.workspace_for_roots(&["/tmp"]).unwrap(), workspace);
+
+        workspace.zoomed_pane = Some(vec![0]);
+        workspace.active_pane = None;
+        db.save_workspace(workspace.clone()).await;
+        assert_eq!(db.workspace_for_roots(&["/tmp"]).unwrap(), workspace);
+    }
+
+    #[test]
+    fn test_flagged_active_pane_path() {
+        let pane = |active| SerializedPaneGroup::Pane(SerializedPane::new(vec![], active));
+
+        assert_eq!(pane(false).flagged_active_pane_path(), None);
+        assert_eq!(pane(true).flagged_active_pane_path(), Some(vec![]));
+
+        // Databases from before the active_pane column only have the per-pane flag
+        let center_group = group(
+            Axis::Horizontal,
+            vec![
+                pane(false),
+                group(Axis::Vertical, vec![pane(false), pane(true)]),
+                pane(true),
+            ],
+        );
+        assert_eq!(center_group.flagged_active_pane_path(), Some(vec![1, 1]));
+    }
+
+    #[gpui::test]
+    async fn test_locked_pane_serialization() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_locked_pane_serialization").await);
+
+        // The locked pane has no items, but is kept rather than filtered out.
+        let center_group = group(
+            Axis::Horizontal,
+            vec![
+                SerializedPaneGroup::Pane(SerializedPane::new(
+                    vec![SerializedItem::new("Terminal", 1, true)],
+                    false,
+                )),
+                SerializedPaneGroup::Pane(SerializedPane::new(vec![], false).with_locked(true)),
+            ],
+        );
+        let workspace = default_workspace(&["/tmp"], &center_group);
+        db.save_workspace(workspace.clone()).await;
+        assert_eq!(db.workspace_for_roots(&["/tmp"]).unwrap(), workspace);
+    }
+
+    #[gpui::test]
+    async fn test_file_db_persistence(cx: &mut gpui::TestAppContext) {
+        env_logger::try_init().ok();
+        cx.executor().allow_parking();
+
+        let tempdir = tempfile::Builder::new()
+            .prefix("WorkspaceDb")
+            .tempdir()
+            .unwrap();
+        let db_path = tempdir.path().join("db.sqlite");
+
+        let center_group = group(
+            Axis::Vertical,
+            vec![
+                SerializedPaneGroup::Pane(SerializedPane::new(vec![], true)),
+                SerializedPaneGroup::Pane(SerializedPane::new(vec![], false)),
+            ],
+        );
+        let workspace = default_workspace(&["/tmp"], &center_group);
+        {
+            let db = WorkspaceDb(open_file_db(&db_path).await.unwrap());
+            db.save_workspace(workspace.clone()).await;
+        }
+
+        let db = WorkspaceDb(open_file_db(&db_path).await.unwrap());
+        assert!(db.persistent());
+        assert_eq!(db.workspace_for_roots(&["/tmp"]).unwrap(), workspace);
+        assert_eq!(
+            db.select_row::<String>("PRAGMA journal_mode").unwrap()()
+                .unwrap()
+                .as_deref(),
+            Some("wal")
+        );
+        assert_eq!(
+            db.select_row::<bool>("PRAGMA foreign_keys").unwrap()().unwrap(),
+            Some(true)
+        );
+    }
+
+    #[gpui::test]
+    async fn test_left_dock_serialization() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_left_dock_serialization").await);
+
+        let mut workspace = default_workspace(&["/tmp"], &Default::default());
+        workspace.docks = DockStructure {
+            left: DockData {
+                visible: true,
+                active_panel: Some("TerminalPanel".to_string()),
+                zoom: true,
+                size: None,
+            },
+            right: Default::default(),
+            bottom: DockData {
+                visible: false,
+                active_panel: Some("TerminalPanel".to_string()),
+                zoom: false,
+                size: None,
+            },
+        };
+
+        db.save_workspace(workspace.clone()).await;
+        assert_eq!(db.workspace_for_roots(&["/tmp"]).unwrap(), workspace);
+        assert_eq!(db.get_docks(workspace.id).unwrap(), Some(workspace.docks));
+        assert_eq!(db.get_docks(workspace.id + 1).unwrap(), None);
+    }
+
+    #[gpui::test]
+    async fn test_dock_size_serialization() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_dock_size_serialization").await);
+
+        let mut workspace = default_workspace(&["/tmp"], &Default::default());
+        workspace.docks.left = DockData {
+            visible: true,
+            active_panel: Some("ProjectPanel".to_string()),
+            zoom: false,
+            size: Some(320.),
+        };
+        workspace.docks.bottom = DockData {
+            visible: true,
+            active_panel: Some("TerminalPanel".to_string()),
+            zoom: false,
+            size: Some(180.5),
+        };
+        db.save_workspace(workspace.clone()).await;
+        assert_eq!(db.workspace_for_roots(&["/tmp"]).unwrap(), workspace);
+        assert_eq!(
+            db.get_docks(workspace.id).unwrap(),
+            Some(workspace.docks.clone())
+        );
+
+        // Sizes that can't be right are dropped or clamped on load.
+        workspace.docks.left.size = Some(-20.);
+        workspace.docks.bottom.size = Some(1e9);
+        db.save_workspace(workspace.clone()).await;
+        let docks = db.get_docks(workspace.id).unwrap().unwrap();
+        assert_eq!(docks.left.size, None);
+        assert_eq!(docks.bottom.size, Some(10_000.));
+    }
+
     #[gpui::test]
     async fn test_cleanup_panes() {
         env_logger::try_init().ok();
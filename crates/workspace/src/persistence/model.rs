@@ -6,16 +6,19 @@ use db::sqlez::{
     bindable::{Bind, Column, StaticColumnCount},
     statement::Statement,
 };
-use gpui::{AsyncWindowContext, Model, Task, View, WeakView, WindowBounds};
+use gpui::{point, size, AsyncWindowContext, Bounds, Model, Task, View, WeakView, WindowBounds};
 use project::Project;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::{
     path::{Path, PathBuf},
     sync::Arc,
+    time::SystemTime,
 };
 use util::ResultExt;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "Vec<PathBuf>", into = "Vec<PathBuf>")]
 pub struct WorkspaceLocation(Arc<Vec<PathBuf>>);
 
 impl WorkspaceLocation {
@@ -24,17 +27,31 @@ impl WorkspaceLocation {
     }
 }
 
+/// Roots are canonicalized when they exist, so that `..` segments and symlinks lead to the
+/// same workspace, and are otherwise only cleaned of trailing slashes and `.` segments.
+/// Locations stored before roots were normalized aren't rewritten, so a root saved with a
+/// trailing slash or through a symlink starts a new workspace the next time it's opened.
 impl<P: AsRef<Path>, T: IntoIterator<Item = P>> From<T> for WorkspaceLocation {
     fn from(iterator: T) -> Self {
         let mut roots = iterator
             .into_iter()
-            .map(|p| p.as_ref().to_path_buf())
+            .map(|p| {
+                let p = p.as_ref();
+                std::fs::canonicalize(p).unwrap_or_else(|_| p.components().collect())
+            })
             .collect::<Vec<_>>();
         roots.sort();
+        roots.dedup();
         Self(Arc::new(roots))
     }
 }
 
+impl From<WorkspaceLocation> for Vec<PathBuf> {
+    fn from(location: WorkspaceLocation) -> Self {
+        location.0.as_ref().clone()
+    }
+}
+
 impl StaticColumnCount for WorkspaceLocation {}
 impl Bind for &WorkspaceLocation {
     fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
@@ -54,17 +71,49 @@ impl Column for WorkspaceLocation {
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub(crate) struct SerializedWorkspace {
+/// A stored workspace. Its JSON form, used to back up and debug the stored layout state,
+/// comes from the serde derives.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SerializedWorkspace {
     pub(crate) id: WorkspaceId,
     pub(crate) location: WorkspaceLocation,
     pub(crate) center_group: SerializedPaneGroup,
+    #[serde(
+        default,
+        serialize_with = "serialize_window_bounds",
+        deserialize_with = "deserialize_window_bounds"
+    )]
     pub(crate) bounds: Option<WindowBounds>,
+    #[serde(default)]
     pub(crate) display: Option<Uuid>,
     pub(crate) docks: DockStructure,
+    /// The member indices leading from the root of `center_group` to the zoomed pane.
+    #[serde(default)]
+    pub(crate) zoomed_pane: Option<Vec<usize>>,
+    /// The member indices leading from the root of `center_group` to the active pane.
+    #[serde(default)]
+    pub(crate) active_pane: Option<Vec<usize>>,
+    /// When this workspace was last opened, if ever. Recorded separately by
+    /// `WorkspaceDb::record_opened` and ignored when saving.
+    #[serde(default)]
+    pub(crate) last_opened: Option<SystemTime>,
+}
+
+impl SerializedWorkspace {
+    pub fn id(&self) -> WorkspaceId {
+        self.id
+    }
+
+    pub fn location(&self) -> &WorkspaceLocation {
+        &self.location
+    }
+
+    pub fn last_opened(&self) -> Option<SystemTime> {
+        self.last_opened
+    }
 }
 
-#[derive(Debug, PartialEq, Clone, Default)]
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
 pub struct DockStructure {
     pub(crate) left: DockData,
     pub(crate) right: DockData,
@@ -95,11 +144,24 @@ impl Bind for DockStructure {
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Default)]
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
 pub struct DockData {
     pub(crate) visible: bool,
     pub(crate) active_panel: Option<String>,
     pub(crate) zoom: bool,
+    /// The active panel's width or height in pixels, if the dock was open.
+    #[serde(default, deserialize_with = "deserialize_dock_size")]
+    pub(crate) size: Option<f32>,
+}
+
+impl DockData {
+    /// Larger sizes than this are treated as corrupt and clamped when loading.
+    const MAX_SIZE: f32 = 10_000.;
+
+    fn valid_size(size: Option<f32>) -> Option<f32> {
+        size.filter(|size| size.is_finite() && *size > 0.)
+            .map(|size| size.min(Self::MAX_SIZE))
+    }
 }
 
 impl Column for DockData {
@@ -107,11 +169,13 @@ impl Column for DockData {
         let (visible, next_index) = Option::<bool>::column(statement, start_index)?;
         let (active_panel, next_index) = Option::<String>::column(statement, next_index)?;
         let (zoom, next_index) = Option::<bool>::column(statement, next_index)?;
+        let (size, next_index) = Option::<f32>::column(statement, next_index)?;
         Ok((
             DockData {
                 visible: visible.unwrap_or(false),
                 active_panel,
                 zoom: zoom.unwrap_or(false),
+                size: Self::valid_size(size),
             },
             next_index,
         ))
@@ -122,12 +186,23 @@ impl Bind for DockData {
     fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
         let next_index = statement.bind(&self.visible, start_index)?;
         let next_index = statement.bind(&self.active_panel, next_index)?;
-        statement.bind(&self.zoom, next_index)
+        let next_index = statement.bind(&self.zoom, next_index)?;
+        statement.bind(&self.size, next_index)
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub(crate) enum SerializedPaneGroup {
+/// The shape of a saved center layout, as returned by `WorkspaceDb::layout_stats`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LayoutStats {
+    pub pane_count: usize,
+    /// How deeply axes are nested, where a lone pane has a depth of 0.
+    pub max_depth: usize,
+    pub axis_count: usize,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SerializedPaneGroup {
     Group {
         axis: SerializedAxis,
         flexes: Option<Vec<f32>>,
@@ -142,11 +217,27 @@ impl Default for SerializedPaneGroup {
         Self::Pane(SerializedPane {
             children: vec![SerializedItem::default()],
             active: false,
+            locked: false,
         })
     }
 }
 
 impl SerializedPaneGroup {
+    /// The path to the first pane whose `active` flag is set. Workspaces saved before the
+    /// active pane had its own column only recorded it this way.
+    pub(crate) fn flagged_active_pane_path(&self) -> Option<Vec<usize>> {
+        match self {
+            SerializedPaneGroup::Group { children, .. } => {
+                children.iter().enumerate().find_map(|(ix, child)| {
+                    let mut path = child.flagged_active_pane_path()?;
+                    path.insert(0, ix);
+                    Some(path)
+                })
+            }
+            SerializedPaneGroup::Pane(pane) => pane.active.then(Vec::new),
+        }
+    }
+
     #[async_recursion(?Send)]
     pub(crate) async fn deserialize(
         self,
@@ -154,24 +245,39 @@ impl SerializedPaneGroup {
         workspace_id: WorkspaceId,
         workspace: WeakView<Workspace>,
         cx: &mut AsyncWindowContext,
-    ) -> Option<(Member, Option<View<Pane>>, Vec<Option<Box<dyn ItemHandle>>>)> {
+    ) -> Option<(
+        Member,
+        Vec<Option<Box<dyn ItemHandle>>>,
+        Vec<View<Pane>>,
+        Vec<(Vec<usize>, View<Pane>)>,
+    )> {
         match self {
             SerializedPaneGroup::Group {
                 axis,
                 children,
                 flexes,
             } => {
-                let mut current_active_pane = None;
                 let mut members = Vec::new();
+                let mut member_indices = Vec::new();
                 let mut items = Vec::new();
-                for child in children {
-                    if let Some((new_member, active_pane, new_items)) = child
+                let mut locked_panes = Vec::new();
+                // Paths are relative to the stored layout, so they still identify panes
+                // after their failed siblings are dropped
+                let mut pane_paths = Vec::new();
+                let child_count = children.len();
+                for (ix, child) in children.into_iter().enumerate() {
+                    if let Some((new_member, new_items, new_locked_panes, new_pane_paths)) = child
                         .deserialize(project, workspace_id, workspace.clone(), cx)
                         .await
                     {
                         members.push(new_member);
+                        member_indices.push(ix);
                         items.extend(new_items);
-                        current_active_pane = current_active_pane.or(active_pane);
+                        locked_panes.extend(new_locked_panes);
+                        pane_paths.extend(new_pane_paths.into_iter().map(|(mut path, pane)| {
+                            path.insert(0, ix);
+                            (path, pane)
+                        }));
                     }
                 }
 
@@ -180,28 +286,38 @@ impl SerializedPaneGroup {
                 }
 
                 if members.len() == 1 {
-                    return Some((members.remove(0), current_active_pane, items));
+                    return Some((members.remove(0), items, locked_panes, pane_paths));
                 }
 
+                let flexes =
+                    flexes.and_then(|flexes| restored_flexes(flexes, child_count, &member_indices));
+
                 Some((
                     Member::Axis(PaneAxis::load(axis.0, members, flexes)),
-                    current_active_pane,
                     items,
+                    locked_panes,
+                    pane_paths,
                 ))
             }
             SerializedPaneGroup::Pane(serialized_pane) => {
                 let pane = workspace
                     .update(cx, |workspace, cx| workspace.add_pane(cx).downgrade())
                     .log_err()?;
-                let active = serialized_pane.active;
+                let locked = serialized_pane.locked;
                 let new_items = serialized_pane
                     .deserialize_to(project, &pane, workspace_id, workspace.clone(), cx)
                     .await
                     .log_err()?;
 
-                if pane.update(cx, |pane, _| pane.items_len() != 0).log_err()? {
+                // A locked pane is kept even when none of its items could be restored
+                if locked || pane.update(cx, |pane, _| pane.items_len() != 0).log_err()? {
                     let pane = pane.upgrade()?;
-                    Some((Member::Pane(pane.clone()), active.then(|| pane), new_items))
+                    Some((
+                        Member::Pane(pane.clone()),
+                        new_items,
+                        locked.then(|| pane.clone()).into_iter().collect(),
+                        vec![(Vec::new(), pane)],
+                    ))
                 } else {
                     let pane = pane.upgrade()?;
                     workspace
@@ -214,15 +330,56 @@ impl SerializedPaneGroup {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Default, Clone)]
+/// Picks the stored flexes of the children at `member_indices` out of `flexes`.
+///
+/// Children that failed to deserialize take their share of the space with them, so the
+/// remaining flexes are rescaled to keep their relative sizes. Returns `None`, which gives
+/// every member an equal share, when the stored flexes don't match the stored children or
+/// leave nothing to rescale.
+fn restored_flexes(
+    flexes: Vec<f32>,
+    child_count: usize,
+    member_indices: &[usize],
+) -> Option<Vec<f32>> {
+    if flexes.len() != child_count {
+        return None;
+    }
+    let flexes = member_indices
+        .iter()
+        .map(|ix| flexes[*ix])
+        .collect::<Vec<_>>();
+    let total = flexes.iter().sum::<f32>();
+    if total <= 0. {
+        return None;
+    }
+    Some(
+        flexes
+            .iter()
+            .map(|flex| flex * flexes.len() as f32 / total)
+            .collect(),
+    )
+}
+
+#[derive(Debug, PartialEq, Eq, Default, Clone, Serialize, Deserialize)]
 pub struct SerializedPane {
     pub(crate) active: bool,
     pub(crate) children: Vec<SerializedItem>,
+    #[serde(default)]
+    pub(crate) locked: bool,
 }
 
 impl SerializedPane {
     pub fn new(children: Vec<SerializedItem>, active: bool) -> Self {
-        SerializedPane { children, active }
+        SerializedPane {
+            children,
+            active,
+            locked: false,
+        }
+    }
+
+    pub fn with_locked(mut self, locked: bool) -> Self {
+        self.locked = locked;
+        self
     }
 
     pub async fn deserialize_to(
@@ -278,7 +435,7 @@ pub type GroupId = i64;
 pub type PaneId = i64;
 pub type ItemId = u64;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct SerializedItem {
     pub kind: Arc<str>,
     pub item_id: ItemId,
@@ -334,3 +491,71 @@ impl Column for SerializedItem {
         ))
     }
 }
+
+/// The JSON form of [`WindowBounds`], which gpui doesn't make serializable.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum WindowBoundsJson {
+    Fullscreen,
+    Maximized,
+    Fixed {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    },
+}
+
+impl From<WindowBounds> for WindowBoundsJson {
+    fn from(bounds: WindowBounds) -> Self {
+        match bounds {
+            WindowBounds::Fullscreen => Self::Fullscreen,
+            WindowBounds::Maximized => Self::Maximized,
+            WindowBounds::Fixed(bounds) => Self::Fixed {
+                x: bounds.origin.x.into(),
+                y: bounds.origin.y.into(),
+                width: bounds.size.width.into(),
+                height: bounds.size.height.into(),
+            },
+        }
+    }
+}
+
+impl From<WindowBoundsJson> for WindowBounds {
+    fn from(bounds: WindowBoundsJson) -> Self {
+        match bounds {
+            WindowBoundsJson::Fullscreen => Self::Fullscreen,
+            WindowBoundsJson::Maximized => Self::Maximized,
+            WindowBoundsJson::Fixed {
+                x,
+                y,
+                width,
+                height,
+            } => Self::Fixed(Bounds {
+                origin: point(x.into(), y.into()),
+                size: size(width.into(), height.into()),
+            }),
+        }
+    }
+}
+
+fn serialize_window_bounds<S: Serializer>(
+    bounds: &Option<WindowBounds>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    bounds.map(WindowBoundsJson::from).serialize(serializer)
+}
+
+fn deserialize_window_bounds<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<WindowBounds>, D::Error> {
+    Ok(Option::<WindowBoundsJson>::deserialize(deserializer)?.map(WindowBounds::from))
+}
+
+fn deserialize_dock_size<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<f32>, D::Error> {
+    Ok(DockData::valid_size(Option::<f32>::deserialize(
+        deserializer,
+    )?))
+}
@@ -25,13 +25,13 @@ use futures::{
     Future, FutureExt, StreamExt,
 };
 use gpui::{
-    actions, canvas, div, impl_actions, point, px, size, Action, AnyElement, AnyModel, AnyView,
-    AnyWeakView, AppContext, AsyncAppContext, AsyncWindowContext, Bounds, Context, Div,
-    DragMoveEvent, Element, ElementContext, Entity, EntityId, EventEmitter, FocusHandle,
-    FocusableView, GlobalPixels, InteractiveElement, IntoElement, KeyContext, LayoutId,
-    ManagedView, Model, ModelContext, ParentElement, PathPromptOptions, Pixels, Point, PromptLevel,
-    Render, Size, Styled, Subscription, Task, View, ViewContext, VisualContext, WeakView,
-    WindowBounds, WindowContext, WindowHandle, WindowOptions,
+    actions, canvas, div, impl_actions, overlay, point, px, size, Action, AnchorCorner, AnyElement,
+    AnyModel, AnyView, AnyWeakView, AppContext, AsyncAppContext, AsyncWindowContext, Bounds,
+    Context, DismissEvent, Div, DragMoveEvent, Element, ElementContext, Entity, EntityId,
+    EventEmitter, FocusHandle, FocusableView, GlobalPixels, InteractiveElement, IntoElement,
+    KeyContext, LayoutId, ManagedView, Model, ModelContext, ParentElement, PathPromptOptions,
+    Pixels, Point, PromptLevel, Render, Size, Styled, Subscription, Task, View, ViewContext,
+    VisualContext, WeakView, WindowBounds, WindowContext, WindowHandle, WindowOptions,
 };
 use item::{FollowableItem, FollowableItemHandle, Item, ItemHandle, ItemSettings, ProjectItem};
 use itertools::Itertools;
@@ -42,10 +42,14 @@ use node_runtime::NodeRuntime;
 use notifications::{simple_message_notification::MessageNotification, NotificationHandle};
 pub use pane::*;
 pub use pane_group::*;
-use persistence::{model::SerializedWorkspace, SerializedWindowsBounds, DB};
+use parking_lot::Mutex;
+use persistence::DB;
 pub use persistence::{
-    model::{ItemId, WorkspaceLocation},
-    WorkspaceDb, DB as WORKSPACE_DB,
+    model::{
+        GroupId, ItemId, PaneId, SerializedItem, SerializedPane, SerializedPaneGroup,
+        SerializedWorkspace, WorkspaceLocation,
+    },
+    SerializedAxis, WorkspaceDb, DB as WORKSPACE_DB,
 };
 use postage::stream::Stream;
 use project::{Project, ProjectEntryId, ProjectPath, Worktree, WorktreeId};
@@ -60,21 +64,20 @@ use std::{
     cmp, env,
     path::{Path, PathBuf},
     sync::{atomic::AtomicUsize, Arc},
-    time::Duration,
+    time::{Duration, SystemTime},
 };
 use theme::{ActiveTheme, ThemeSettings};
 pub use toolbar::{Toolbar, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView};
 pub use ui;
-use ui::Label;
+use ui::{ContextMenu, Label};
 use util::ResultExt;
 use uuid::Uuid;
-pub use workspace_settings::{AutosaveSetting, WorkspaceSettings};
-
-use crate::persistence::{
-    model::{DockData, DockStructure, SerializedItem, SerializedPane, SerializedPaneGroup},
-    SerializedAxis,
+pub use workspace_settings::{
+    AutosaveSetting, NewPanePlacement, ScrollResizeModifier, WorkspaceSettings,
 };
 
+use crate::persistence::model::{DockData, DockStructure};
+
 lazy_static! {
     static ref ZED_WINDOW_SIZE: Option<Size<GlobalPixels>> = env::var("ZED_WINDOW_SIZE")
         .ok()
@@ -103,6 +106,8 @@ actions!(
         ReloadActiveItem,
         ActivatePreviousPane,
         ActivateNextPane,
+        ResetPaneSizes,
+        FocusPaneDivider,
         FollowNextCollaborator,
         NewTerminal,
         NewCenterTerminal,
@@ -130,6 +135,9 @@ pub struct ActivatePane(pub usize);
 #[derive(Clone, Deserialize, PartialEq)]
 pub struct ActivatePaneInDirection(pub SplitDirection);
 
+#[derive(Clone, Deserialize, PartialEq)]
+pub struct ActivateEdgePane(pub SplitDirection);
+
 #[derive(Clone, Deserialize, PartialEq)]
 pub struct SwapPaneInDirection(pub SplitDirection);
 
@@ -159,6 +167,7 @@ impl_actions!(
     [
         ActivatePane,
         ActivatePaneInDirection,
+        ActivateEdgePane,
         CloseAllItemsAndPanes,
         NewFileInDirection,
         OpenTerminal,
@@ -443,6 +452,8 @@ pub struct Workspace {
     zoomed: Option<AnyWeakView>,
     zoomed_position: Option<DockPosition>,
     center: PaneGroup,
+    resizing_panes: bool,
+    pane_handle_menu: Option<HandleMenu>,
     left_dock: View<Dock>,
     bottom_dock: View<Dock>,
     right_dock: View<Dock>,
@@ -450,6 +461,8 @@ pub struct Workspace {
     panes_by_item: HashMap<EntityId, WeakView<Pane>>,
     active_pane: View<Pane>,
     last_active_center_pane: Option<WeakView<Pane>>,
+    /// Center panes, most recently activated first.
+    mru_panes: Vec<View<Pane>>,
     last_active_view_id: Option<proto::ViewId>,
     status_bar: View<StatusBar>,
     modal_layer: View<ModalLayer>,
@@ -467,12 +480,15 @@ pub struct Workspace {
     _apply_leader_updates: Task<Result<()>>,
     _observe_current_user: Task<Result<()>>,
     _schedule_serialize: Option<Task<()>>,
+    _schedule_layout_save: Option<Task<()>>,
     pane_history_timestamp: Arc<AtomicUsize>,
     bounds: Bounds<Pixels>,
 }
 
 impl EventEmitter<Event> for Workspace {}
 
+impl EventEmitter<PaneGroupEvent> for Workspace {}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct ViewId {
     pub creator: PeerId,
@@ -623,7 +639,11 @@ impl Workspace {
             active_call = Some((call, subscriptions));
         }
 
+        let handle = cx.view().clone();
         let subscriptions = vec![
+            cx.subscribe(&handle, |this, _, _: &PaneGroupEvent, cx| {
+                this.schedule_layout_save(cx)
+            }),
             cx.observe_window_activation(Self::on_window_activation_changed),
             cx.observe_window_bounds(move |_, cx| {
                 if let Some(display) = cx.display() {
@@ -637,11 +657,7 @@ impl Workspace {
 
                     if let Some(display_uuid) = display.uuid().log_err() {
                         cx.background_executor()
-                            .spawn(DB.set_window_bounds(
-                                workspace_id,
-                                SerializedWindowsBounds(bounds),
-                                display_uuid,
-                            ))
+                            .spawn(DB.save_window_bounds(workspace_id, bounds, display_uuid))
                             .detach_and_log_err(cx);
                     }
                 }
@@ -675,10 +691,13 @@ impl Workspace {
             zoomed: None,
             zoomed_position: None,
             center: PaneGroup::new(center_pane.clone()),
+            resizing_panes: false,
+            pane_handle_menu: None,
             panes: vec![center_pane.clone()],
             panes_by_item: Default::default(),
             active_pane: center_pane.clone(),
             last_active_center_pane: Some(center_pane.downgrade()),
+            mru_panes: vec![center_pane.clone()],
             last_active_view_id: None,
             status_bar,
             modal_layer,
@@ -697,6 +716,7 @@ impl Workspace {
             _observe_current_user,
             _apply_leader_updates,
             _schedule_serialize: None,
+            _schedule_layout_save: None,
             leader_updates_tx,
             _subscriptions: subscriptions,
             pane_history_timestamp,
@@ -756,6 +776,9 @@ impl Workspace {
             } else {
                 DB.next_id().await.unwrap_or(0)
             };
+            DB.record_opened(workspace_id, SystemTime::now())
+                .await
+                .log_err();
 
             let window = if let Some(window) = requesting_window {
                 cx.update_window(window.into(), |_, cx| {
@@ -1139,6 +1162,7 @@ impl Workspace {
         quitting: bool,
         cx: &mut ViewContext<Self>,
     ) -> Task<Result<bool>> {
+        self.flush_layout_save(cx);
         let active_call = self.active_call().cloned();
         let window = cx.window_handle();
 
@@ -2043,6 +2067,11 @@ impl Workspace {
         }
     }
 
+    /// Focuses the pane furthest towards `direction`, see [`PaneGroup::edge_pane`].
+    pub fn activate_edge_pane(&mut self, direction: SplitDirection, cx: &mut WindowContext) {
+        cx.focus_view(&self.center.edge_pane(direction));
+    }
+
     pub fn swap_pane_in_direction(
         &mut self,
         direction: SplitDirection,
@@ -2052,11 +2081,133 @@ impl Workspace {
             .find_pane_in_direction(direction, cx)
             .map(|pane| pane.clone())
         {
-            self.center.swap(&self.active_pane.clone(), &to);
+            self.center.swap(&self.active_pane.clone(), &to).log_err();
+            cx.notify();
+        }
+    }
+
+    pub fn resize_active_pane(
+        &mut self,
+        direction: SplitDirection,
+        amount: f32,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if self
+            .center
+            .resize_active(&self.active_pane, direction, amount, cx)
+        {
+            cx.emit(PaneGroupEvent::Resized);
+            cx.notify();
+        }
+    }
+
+    pub fn promote_active_pane(
+        &mut self,
+        direction: SplitDirection,
+        grow: f32,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if let Some(true) = self
+            .center
+            .promote(&self.active_pane, direction, grow, cx)
+            .log_err()
+        {
+            cx.emit(PaneGroupEvent::Resized);
+            cx.notify();
+        }
+    }
+
+    /// Whether a divider between center panes is being dragged. Panes can use this to put
+    /// off expensive work until the drag ends, when the workspace is notified again.
+    pub fn is_resizing_panes(&self) -> bool {
+        self.resizing_panes
+    }
+
+    pub(crate) fn set_resizing_panes(&mut self, resizing: bool, cx: &mut ViewContext<Self>) {
+        if self.resizing_panes != resizing {
+            self.resizing_panes = resizing;
+            cx.notify();
+        }
+    }
+
+    /// Opens the context menu of handle `ix` of the center axis laid out with `flexes`.
+    pub(crate) fn deploy_pane_handle_menu(
+        &mut self,
+        flexes: Arc<Mutex<Vec<f32>>>,
+        ix: usize,
+        position: Point<Pixels>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let entry = |action: HandleAction| {
+            let workspace = self.weak_self.clone();
+            let flexes = flexes.clone();
+            move |cx: &mut WindowContext| {
+                workspace
+                    .update(cx, |workspace, cx| {
+                        workspace.apply_pane_handle_action(&flexes, ix, action, cx)
+                    })
+                    .log_err();
+            }
+        };
+        let menu = ContextMenu::build(cx, |menu, _| {
+            menu.entry("Equalize", None, entry(HandleAction::Equalize))
+                .entry("Reset", None, entry(HandleAction::Reset))
+                .entry("Rotate Axis", None, entry(HandleAction::RotateAxis))
+        });
+        cx.focus_view(&menu);
+        let subscription = cx.subscribe(&menu, |this, _, _: &DismissEvent, cx| {
+            this.pane_handle_menu.take();
             cx.notify();
+        });
+        self.pane_handle_menu = Some(HandleMenu {
+            menu,
+            position,
+            flexes,
+            ix,
+            _subscription: subscription,
+        });
+        cx.notify();
+    }
+
+    fn apply_pane_handle_action(
+        &mut self,
+        flexes: &Arc<Mutex<Vec<f32>>>,
+        ix: usize,
+        action: HandleAction,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if self
+            .center
+            .apply_handle_action(flexes, ix, action)
+            .log_err()
+            .is_none()
+        {
+            return;
+        }
+        cx.emit(match action {
+            HandleAction::RotateAxis => PaneGroupEvent::Rotated,
+            HandleAction::Equalize | HandleAction::Reset => PaneGroupEvent::Resized,
+        });
+        cx.notify();
+    }
+
+    /// Focuses the divider next to the active pane, so that it can be moved with the arrow
+    /// keys. Escape gives focus back to the pane.
+    pub fn focus_pane_divider(&mut self, cx: &mut ViewContext<Self>) {
+        if !WorkspaceSettings::get_global(cx).resizable_panes {
+            return;
+        }
+        if let Some(focus_handle) = self.center.divider_focus_handle(&self.active_pane) {
+            cx.focus(&focus_handle);
         }
     }
 
+    pub fn reset_pane_sizes(&mut self, cx: &mut ViewContext<Self>) {
+        self.center.equalize();
+        cx.emit(PaneGroupEvent::Resized);
+        cx.notify();
+    }
+
     fn find_pane_in_direction(
         &mut self,
         direction: SplitDirection,
@@ -2099,6 +2250,7 @@ impl Workspace {
             self.active_item_path_changed(cx);
             self.last_active_center_pane = Some(pane.downgrade());
         }
+        self.push_mru_pane(&pane);
 
         self.dismiss_zoomed_items_to_reveal(None, cx);
         if pane.read(cx).is_zoomed() {
@@ -2112,6 +2264,11 @@ impl Workspace {
         cx.notify();
     }
 
+    fn push_mru_pane(&mut self, pane: &View<Pane>) {
+        self.mru_panes.retain(|p| p != pane);
+        self.mru_panes.insert(0, pane.clone());
+    }
+
     fn handle_pane_event(
         &mut self,
         pane: View<Pane>,
@@ -2121,9 +2278,16 @@ impl Workspace {
         match event {
             pane::Event::AddItem { item } => item.added_to_pane(self, pane, cx),
             pane::Event::Split(direction) => {
-                self.split_and_clone(pane, *direction, cx);
+                if self.center.can_split(&pane, *direction, cx) {
+                    self.split_and_clone(pane, *direction, cx);
+                }
+                // Splitting emits a `PaneGroupEvent`, whose layout save covers this change.
+                return;
+            }
+            pane::Event::Remove => {
+                self.remove_pane(pane, cx);
+                return;
             }
-            pane::Event::Remove => self.remove_pane(pane, cx),
             pane::Event::ActivateItem { local } => {
                 if *local {
                     self.unfollow(&pane, cx);
@@ -2172,6 +2336,8 @@ impl Workspace {
         self.serialize_workspace(cx);
     }
 
+    /// Splits `pane_to_split` and returns the new pane, or `pane_to_split` itself if the
+    /// center can't take another pane.
     pub fn split_pane(
         &mut self,
         pane_to_split: View<Pane>,
@@ -2179,13 +2345,40 @@ impl Workspace {
         cx: &mut ViewContext<Self>,
     ) -> View<Pane> {
         let new_pane = self.add_pane(cx);
-        self.center
-            .split(&pane_to_split, &new_pane, split_direction)
-            .unwrap();
+        let split_direction = WorkspaceSettings::get_global(cx)
+            .new_pane_placement
+            .direction(split_direction);
+        let new_pane = self
+            .split_center_pane(&pane_to_split, new_pane, split_direction, cx)
+            .unwrap_or(pane_to_split);
         cx.notify();
         new_pane
     }
 
+    /// Places `new_pane`, which was just added, next to `pane_to_split` in the center. When
+    /// the split fails, such as when the center is at its pane limit, `new_pane` is removed
+    /// again and focus returns to `pane_to_split`.
+    fn split_center_pane(
+        &mut self,
+        pane_to_split: &View<Pane>,
+        new_pane: View<Pane>,
+        direction: SplitDirection,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<View<Pane>> {
+        match self.center.split(pane_to_split, &new_pane, direction) {
+            Ok(_) => {
+                cx.emit(PaneGroupEvent::Split);
+                Some(new_pane)
+            }
+            Err(error) => {
+                log::error!("Failed to split pane: {error}");
+                self.force_remove_pane(&new_pane, cx);
+                pane_to_split.update(cx, |pane, cx| pane.focus(cx));
+                None
+            }
+        }
+    }
+
     pub fn split_and_clone(
         &mut self,
         pane: View<Pane>,
@@ -2196,8 +2389,10 @@ impl Workspace {
         let maybe_pane_handle = if let Some(clone) = item.clone_on_split(self.database_id(), cx) {
             let new_pane = self.add_pane(cx);
             new_pane.update(cx, |pane, cx| pane.add_item(clone, true, true, None, cx));
-            self.center.split(&pane, &new_pane, direction).unwrap();
-            Some(new_pane)
+            let direction = WorkspaceSettings::get_global(cx)
+                .new_pane_placement
+                .direction(direction);
+            self.split_center_pane(&pane, new_pane, direction, cx)
         } else {
             None
         };
@@ -2221,10 +2416,11 @@ impl Workspace {
         };
 
         let new_pane = self.add_pane(cx);
-        self.move_item(from.clone(), new_pane.clone(), item_id_to_move, 0, cx);
-        self.center
-            .split(&pane_to_split, &new_pane, split_direction)
-            .unwrap();
+        let Some(new_pane) = self.split_center_pane(&pane_to_split, new_pane, split_direction, cx)
+        else {
+            return;
+        };
+        self.move_item(from.clone(), new_pane, item_id_to_move, 0, cx);
         cx.notify();
     }
 
@@ -2237,9 +2433,7 @@ impl Workspace {
     ) -> Option<Task<Result<()>>> {
         let pane_to_split = pane_to_split.upgrade()?;
         let new_pane = self.add_pane(cx);
-        self.center
-            .split(&pane_to_split, &new_pane, split_direction)
-            .unwrap();
+        let new_pane = self.split_center_pane(&pane_to_split, new_pane, split_direction, cx)?;
 
         let path = self.project.read(cx).path_for_entry(project_entry, cx)?;
         let task = self.open_path(path, Some(new_pane.downgrade()), true, cx);
@@ -2249,6 +2443,24 @@ impl Workspace {
         }))
     }
 
+    /// Moves `pane` to `direction`'s side of `target`, as when the last tab of `pane` is
+    /// dropped on that edge of `target`. Returns `Ok(false)` if `pane` is `target`.
+    pub fn relocate_pane(
+        &mut self,
+        pane: &View<Pane>,
+        target: &View<Pane>,
+        direction: SplitDirection,
+        cx: &mut ViewContext<Self>,
+    ) -> Result<bool> {
+        if !self.center.relocate_pane(pane, target, direction)? {
+            return Ok(false);
+        }
+        pane.update(cx, |pane, cx| pane.focus(cx));
+        cx.emit(PaneGroupEvent::Split);
+        cx.notify();
+        Ok(true)
+    }
+
     pub fn move_item(
         &mut self,
         source: View<Pane>,
@@ -2284,14 +2496,16 @@ impl Workspace {
     }
 
     fn remove_pane(&mut self, pane: View<Pane>, cx: &mut ViewContext<Self>) {
-        if self.center.remove(&pane).unwrap() {
+        if let Some(neighbor) = self.center.remove_returning_neighbor(&pane).unwrap() {
             self.force_remove_pane(&pane, cx);
+            neighbor.update(cx, |pane, cx| pane.focus(cx));
             self.unfollow(&pane, cx);
             self.last_leaders_by_pane.remove(&pane.downgrade());
             for removed_item in pane.read(cx).items() {
                 self.panes_by_item.remove(&removed_item.item_id());
             }
 
+            cx.emit(PaneGroupEvent::Removed);
             cx.notify();
         } else {
             self.active_item_path_changed(cx);
@@ -2302,10 +2516,20 @@ impl Workspace {
         &self.panes
     }
 
+    /// The on-screen bounds of each center pane as of the last frame.
+    pub fn pane_bounds(&self) -> Vec<(View<Pane>, Bounds<Pixels>)> {
+        self.center.pane_bounds()
+    }
+
     pub fn active_pane(&self) -> &View<Pane> {
         &self.active_pane
     }
 
+    /// The center pane that was active before the current one, if there is more than one.
+    pub fn previous_active_pane(&self) -> Option<&View<Pane>> {
+        self.mru_panes.get(1)
+    }
+
     pub fn pane_for(&self, handle: &dyn ItemHandle) -> Option<View<Pane>> {
         let weak_pane = self.panes_by_item.get(&handle.item_id())?;
         weak_pane.upgrade()
@@ -2985,6 +3209,7 @@ impl Workspace {
 
     fn force_remove_pane(&mut self, pane: &View<Pane>, cx: &mut ViewContext<Workspace>) {
         self.panes.retain(|p| p != pane);
+        self.mru_panes.retain(|p| p != pane);
         self.panes
             .last()
             .unwrap()
@@ -3005,6 +3230,28 @@ impl Workspace {
         }));
     }
 
+    /// Saves the workspace once its layout has stopped changing for a moment, so that a
+    /// burst of [`PaneGroupEvent`]s, such as the steps of a drag, is written only once.
+    fn schedule_layout_save(&mut self, cx: &mut ViewContext<Self>) {
+        self._schedule_layout_save = Some(cx.spawn(|this, mut cx| async move {
+            cx.background_executor()
+                .timer(Duration::from_millis(500))
+                .await;
+            this.update(&mut cx, |this, cx| {
+                this._schedule_layout_save.take();
+                this.serialize_workspace(cx);
+            })
+            .log_err();
+        }));
+    }
+
+    /// Saves a layout change that's still waiting out [`Self::schedule_layout_save`]'s delay.
+    fn flush_layout_save(&mut self, cx: &mut ViewContext<Self>) {
+        if self._schedule_layout_save.take().is_some() {
+            self.serialize_workspace(cx);
+        }
+    }
+
     fn serialize_workspace(&self, cx: &mut WindowContext) {
         fn serialize_pane_handle(pane_handle: &View<Pane>, cx: &WindowContext) -> SerializedPane {
             let (items, active) = {
@@ -3027,30 +3274,6 @@ impl Workspace {
             SerializedPane::new(items, active)
         }
 
-        fn build_serialized_pane_group(
-            pane_group: &Member,
-            cx: &WindowContext,
-        ) -> SerializedPaneGroup {
-            match pane_group {
-                Member::Axis(PaneAxis {
-                    axis,
-                    members,
-                    flexes,
-                    bounding_boxes: _,
-                }) => SerializedPaneGroup::Group {
-                    axis: SerializedAxis(*axis),
-                    children: members
-                        .iter()
-                        .map(|member| build_serialized_pane_group(member, cx))
-                        .collect::<Vec<_>>(),
-                    flexes: Some(flexes.lock().clone()),
-                },
-                Member::Pane(pane_handle) => {
-                    SerializedPaneGroup::Pane(serialize_pane_handle(pane_handle, cx))
-                }
-            }
-        }
-
         fn build_serialized_docks(this: &Workspace, cx: &mut WindowContext) -> DockStructure {
             let left_dock = this.left_dock.read(cx);
             let left_visible = left_dock.is_open();
@@ -3061,6 +3284,7 @@ impl Workspace {
                 .visible_panel()
                 .map(|panel| panel.is_zoomed(cx))
                 .unwrap_or(false);
+            let left_dock_size = left_dock.active_panel_size(cx).map(|size| size.0);
 
             let right_dock = this.right_dock.read(cx);
             let right_visible = right_dock.is_open();
@@ -3071,6 +3295,7 @@ impl Workspace {
                 .visible_panel()
                 .map(|panel| panel.is_zoomed(cx))
                 .unwrap_or(false);
+            let right_dock_size = right_dock.active_panel_size(cx).map(|size| size.0);
 
             let bottom_dock = this.bottom_dock.read(cx);
             let bottom_visible = bottom_dock.is_open();
@@ -3081,22 +3306,26 @@ impl Workspace {
                 .visible_panel()
                 .map(|panel| panel.is_zoomed(cx))
                 .unwrap_or(false);
+            let bottom_dock_size = bottom_dock.active_panel_size(cx).map(|size| size.0);
 
             DockStructure {
                 left: DockData {
                     visible: left_visible,
                     active_panel: left_active_panel,
                     zoom: left_dock_zoom,
+                    size: left_dock_size,
                 },
                 right: DockData {
                     visible: right_visible,
                     active_panel: right_active_panel,
                     zoom: right_dock_zoom,
+                    size: right_dock_size,
                 },
                 bottom: DockData {
                     visible: bottom_visible,
                     active_panel: bottom_active_panel,
                     zoom: bottom_dock_zoom,
+                    size: bottom_dock_size,
                 },
             }
         }
@@ -3106,8 +3335,17 @@ impl Workspace {
             //  - with_local_workspace() relies on this to not have other stuff open
             //    when you open your log
             if !location.paths().is_empty() {
-                let center_group = build_serialized_pane_group(&self.center.root, cx);
+                let center_group = self
+                    .center
+                    .to_serialized(|pane| serialize_pane_handle(pane, cx));
                 let docks = build_serialized_docks(self, cx);
+                let zoomed_pane = self
+                    .center
+                    .panes()
+                    .into_iter()
+                    .find(|pane| pane.read(cx).is_zoomed())
+                    .and_then(|pane| self.center.path_to_pane(pane));
+                let active_pane = self.center.path_to_pane(&self.active_pane);
 
                 let serialized_workspace = SerializedWorkspace {
                     id: self.database_id,
@@ -3116,6 +3354,9 @@ impl Workspace {
                     bounds: Default::default(),
                     display: Default::default(),
                     docks,
+                    zoomed_pane,
+                    active_pane,
+                    last_opened: None,
                 };
 
                 cx.spawn(|_| persistence::DB.save_workspace(serialized_workspace))
@@ -3134,9 +3375,10 @@ impl Workspace {
 
             let mut center_group = None;
             let mut center_items = None;
+            let flagged_active_pane = serialized_workspace.center_group.flagged_active_pane_path();
 
             // Traverse the splits tree and add to things
-            if let Some((group, active_pane, items)) = serialized_workspace
+            if let Some((group, items, locked_panes, pane_paths)) = serialized_workspace
                 .center_group
                 .deserialize(
                     &project,
@@ -3147,7 +3389,7 @@ impl Workspace {
                 .await
             {
                 center_items = Some(items);
-                center_group = Some((group, active_pane))
+                center_group = Some((group, locked_panes, pane_paths))
             }
 
             let mut items_by_project_path = cx.update(|cx| {
@@ -3172,11 +3414,28 @@ impl Workspace {
 
             // Remove old panes from workspace panes list
             workspace.update(&mut cx, |workspace, cx| {
-                if let Some((center_group, active_pane)) = center_group {
+                if let Some((center_group, locked_panes, pane_paths)) = center_group {
+                    // The stored paths lead through the layout as it was saved, before any
+                    // panes that failed to restore were dropped
+                    let restored_pane_at = |path: &[usize]| {
+                        pane_paths
+                            .iter()
+                            .find(|(pane_path, _)| pane_path == path)
+                            .map(|(_, pane)| pane.clone())
+                    };
+
                     workspace.remove_panes(workspace.center.root.clone(), cx);
 
                     // Swap workspace center group
                     workspace.center = PaneGroup::with_root(center_group);
+                    for pane in &locked_panes {
+                        workspace.center.set_locked(pane, true).log_err();
+                    }
+                    let active_pane = serialized_workspace
+                        .active_pane
+                        .as_deref()
+                        .and_then(restored_pane_at)
+                        .or_else(|| flagged_active_pane.as_deref().and_then(restored_pane_at));
                     workspace.last_active_center_pane = active_pane.as_ref().map(|p| p.downgrade());
                     if let Some(active_pane) = active_pane {
                         workspace.active_pane = active_pane;
@@ -3184,6 +3443,18 @@ impl Workspace {
                     } else {
                         workspace.active_pane = workspace.center.first_pane().clone();
                     }
+                    let active_pane = workspace.active_pane.clone();
+                    workspace.push_mru_pane(&active_pane);
+
+                    let zoomed_pane = serialized_workspace
+                        .zoomed_pane
+                        .as_deref()
+                        .and_then(restored_pane_at);
+                    if let Some(zoomed_pane) = zoomed_pane {
+                        zoomed_pane.update(cx, |pane, cx| pane.set_zoomed(true, cx));
+                        workspace.zoomed = Some(zoomed_pane.downgrade().into());
+                        workspace.zoomed_position = None;
+                    }
                 }
 
                 let docks = serialized_workspace.docks;
@@ -3243,9 +3514,18 @@ impl Workspace {
                     workspace.activate_pane_in_direction(action.0, cx)
                 }),
             )
+            .on_action(cx.listener(|workspace, action: &ActivateEdgePane, cx| {
+                workspace.activate_edge_pane(action.0, cx)
+            }))
             .on_action(cx.listener(|workspace, action: &SwapPaneInDirection, cx| {
                 workspace.swap_pane_in_direction(action.0, cx)
             }))
+            .on_action(
+                cx.listener(|workspace, _: &ResetPaneSizes, cx| workspace.reset_pane_sizes(cx)),
+            )
+            .on_action(
+                cx.listener(|workspace, _: &FocusPaneDivider, cx| workspace.focus_pane_divider(cx)),
+            )
             .on_action(cx.listener(|this, _: &ToggleLeftDock, cx| {
                 this.toggle_dock(DockPosition::Left, cx);
             }))
@@ -3462,6 +3742,16 @@ fn notify_if_database_failed(workspace: WindowHandle<Workspace>, cx: &mut AsyncA
                             .on_click(|cx| cx.open_url(REPORT_ISSUE_URL))
                     })
                 });
+            } else if (*db::DB_WRITTEN_BY_NEWER_VERSION).load(std::sync::atomic::Ordering::Acquire)
+            {
+                workspace.show_notification_once(1, cx, |cx| {
+                    cx.new_view(|_| {
+                        MessageNotification::new(
+                            "The database was written by a newer version of Zed. \
+                            Changes made in this session will not be saved.",
+                        )
+                    })
+                });
             }
         })
         .log_err();
@@ -3588,6 +3878,12 @@ impl Render for Workspace {
                                         &self.app_state,
                                         cx,
                                     ))
+                                    .children(self.pane_handle_menu.as_ref().map(|handle_menu| {
+                                        overlay()
+                                            .position(handle_menu.position)
+                                            .anchor(AnchorCorner::TopLeft)
+                                            .child(handle_menu.menu.clone())
+                                    }))
                                     .children(
                                         self.zoomed_position
                                             .ne(&Some(DockPosition::Bottom))
@@ -4907,6 +5203,76 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_new_pane_placement(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        let split = |direction, cx: &mut VisualTestContext| {
+            workspace.update(cx, |workspace, cx| {
+                let new_pane = workspace.split_pane(pane.clone(), direction, cx);
+                let panes = workspace.center.panes();
+                let layout = workspace.center.to_layout_string();
+                let new_pane_ix = panes.iter().position(|p| **p == new_pane).unwrap();
+                let pane_ix = panes.iter().position(|p| **p == pane).unwrap();
+                workspace.center = PaneGroup::new(pane.clone());
+                (layout, new_pane_ix > pane_ix)
+            })
+        };
+        let set_placement = |placement, cx: &mut VisualTestContext| {
+            cx.update(|cx| {
+                cx.update_global(|settings: &mut SettingsStore, cx| {
+                    settings.update_user_settings::<WorkspaceSettings>(cx, |settings| {
+                        settings.new_pane_placement = Some(placement);
+                    })
+                })
+            });
+        };
+
+        // By default the new pane goes where the split points.
+        assert_eq!(split(SplitDirection::Up, cx), ("v[a,b]".into(), false));
+        assert_eq!(split(SplitDirection::Right, cx), ("h[a,b]".into(), true));
+
+        set_placement(NewPanePlacement::After, cx);
+        assert_eq!(split(SplitDirection::Up, cx), ("v[a,b]".into(), true));
+        assert_eq!(split(SplitDirection::Left, cx), ("h[a,b]".into(), true));
+
+        set_placement(NewPanePlacement::Before, cx);
+        assert_eq!(split(SplitDirection::Down, cx), ("v[a,b]".into(), false));
+        assert_eq!(split(SplitDirection::Right, cx), ("h[a,b]".into(), false));
+    }
+
+    #[gpui::test]
+    async fn test_mru_panes(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        workspace.update(cx, |workspace, cx| {
+            let a = workspace.active_pane().clone();
+            assert_eq!(workspace.previous_active_pane(), None);
+            let b = workspace.split_pane(a.clone(), SplitDirection::Right, cx);
+            let c = workspace.split_pane(b.clone(), SplitDirection::Down, cx);
+
+            for pane in [&a, &b, &c, &a] {
+                workspace.handle_pane_focused(pane.clone(), cx);
+            }
+            assert_eq!(workspace.mru_panes, [a.clone(), c.clone(), b.clone()]);
+            assert_eq!(workspace.previous_active_pane(), Some(&c));
+
+            workspace.remove_pane(c.clone(), cx);
+            assert_eq!(workspace.mru_panes, [a.clone(), b.clone()]);
+            assert_eq!(workspace.previous_active_pane(), Some(&b));
+            let mut center_panes = workspace.center.panes();
+            center_panes.sort_by_key(|pane| workspace.mru_panes.iter().position(|p| p == *pane));
+            assert_eq!(center_panes, [&a, &b]);
+        });
+    }
+
     #[gpui::test]
     async fn test_toggle_docks_and_panels(cx: &mut gpui::TestAppContext) {
         init_test(cx);
@@ -5078,6 +5444,140 @@ mod tests {
         }
     }
 
+    #[gpui::test]
+    async fn test_pane_group_events(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        cx.update({
+            let events = events.clone();
+            |cx| {
+                cx.subscribe(&workspace, move |_, event: &PaneGroupEvent, _| {
+                    events.borrow_mut().push(*event)
+                })
+                .detach()
+            }
+        });
+
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        let new_pane = workspace.update(cx, |workspace, cx| {
+            workspace.split_pane(pane, SplitDirection::Right, cx)
+        });
+        assert_eq!(
+            events.borrow_mut().drain(..).collect::<Vec<_>>(),
+            [PaneGroupEvent::Split]
+        );
+
+        workspace.update(cx, |workspace, cx| {
+            workspace.resize_active_pane(SplitDirection::Right, 0.1, cx)
+        });
+        assert_eq!(
+            events.borrow_mut().drain(..).collect::<Vec<_>>(),
+            [PaneGroupEvent::Resized]
+        );
+
+        workspace.update(cx, |workspace, cx| workspace.remove_pane(new_pane, cx));
+        assert_eq!(
+            events.borrow_mut().drain(..).collect::<Vec<_>>(),
+            [PaneGroupEvent::Removed]
+        );
+
+        // Without a neighbor there's nothing to resize, so nothing is reported.
+        workspace.update(cx, |workspace, cx| {
+            workspace.resize_active_pane(SplitDirection::Right, 0.1, cx);
+            workspace.promote_active_pane(SplitDirection::Right, 0.1, cx);
+        });
+        assert!(events.borrow().is_empty());
+    }
+
+    #[gpui::test]
+    async fn test_split_pane_at_capacity(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        workspace.update(cx, |workspace, cx| {
+            workspace.center.set_max_panes(Some(1));
+            let pane = workspace.active_pane().clone();
+            let new_pane = workspace.split_pane(pane.clone(), SplitDirection::Right, cx);
+            assert_eq!(new_pane, pane);
+            assert_eq!(workspace.panes(), &[pane.clone()]);
+            assert_eq!(workspace.center.panes(), [&pane]);
+
+            let item = cx.new_view(|cx| TestItem::new(cx));
+            workspace.add_item(Box::new(item), cx);
+            assert!(workspace
+                .split_and_clone(pane.clone(), SplitDirection::Right, cx)
+                .is_none());
+            assert_eq!(workspace.panes(), &[pane]);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_layout_save_debounce(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        cx.executor().allow_parking();
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/layout-save", json!({})).await;
+
+        let project = Project::test(fs, ["/layout-save".as_ref()], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        let workspace_id = persistence::DB.next_id().await.unwrap();
+        workspace.update(cx, |workspace, _| workspace.database_id = workspace_id);
+
+        let saved_flexes = || async {
+            // Writes are queued in order, so this waits for every save issued before it.
+            persistence::DB.write(|_| ()).await;
+            match persistence::DB
+                .workspace_for_roots(&["/layout-save"])?
+                .center_group
+            {
+                SerializedPaneGroup::Group { flexes, .. } => flexes,
+                SerializedPaneGroup::Pane(_) => None,
+            }
+        };
+
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+        workspace.update(cx, |workspace, cx| {
+            workspace.split_pane(pane, SplitDirection::Right, cx)
+        });
+        cx.executor().advance_clock(Duration::from_millis(500));
+        cx.run_until_parked();
+        let split_flexes = saved_flexes().await;
+        assert_eq!(split_flexes, Some(vec![1., 1.]));
+
+        // Each resize restarts the delay, so a quick series of them is saved once.
+        for _ in 0..5 {
+            workspace.update(cx, |workspace, cx| {
+                workspace.resize_active_pane(SplitDirection::Left, 0.05, cx)
+            });
+            cx.executor().advance_clock(Duration::from_millis(100));
+            cx.run_until_parked();
+        }
+        cx.executor().advance_clock(Duration::from_millis(399));
+        cx.run_until_parked();
+        assert_eq!(saved_flexes().await, split_flexes);
+        cx.executor().advance_clock(Duration::from_millis(1));
+        cx.run_until_parked();
+        let resized_flexes = saved_flexes().await;
+        assert_ne!(resized_flexes, split_flexes);
+
+        // A save that's still pending is written when the workspace closes.
+        workspace.update(cx, |workspace, cx| workspace.reset_pane_sizes(cx));
+        cx.run_until_parked();
+        assert_eq!(saved_flexes().await, resized_flexes);
+        workspace
+            .update(cx, |workspace, cx| workspace.prepare_to_close(false, cx))
+            .detach();
+        cx.run_until_parked();
+        assert_eq!(saved_flexes().await, split_flexes);
+    }
+
     #[gpui::test]
     async fn test_panels(cx: &mut gpui::TestAppContext) {
         init_test(cx);
@@ -5307,6 +5807,239 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_load_workspace_skips_unopenable_items(cx: &mut TestAppContext) {
+        use crate::persistence::{model::SerializedPaneGroup, SerializedAxis};
+
+        init_test(cx);
+        cx.update(register_deserializable_item::<TestItem>);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        // Nothing is registered to deserialize "Terminal" items in this crate, so the second
+        // pane has nothing left in it and its slot collapses.
+        let center_group = SerializedPaneGroup::Group {
+            axis: SerializedAxis(gpui::Axis::Horizontal),
+            flexes: Some(vec![0.5, 1.5]),
+            children: vec![
+                SerializedPaneGroup::Pane(SerializedPane::new(
+                    vec![
+                        SerializedItem::new("TestItem", 1, true),
+                        SerializedItem::new("Terminal", 2, false),
+                    ],
+                    true,
+                )),
+                SerializedPaneGroup::Pane(SerializedPane::new(
+                    vec![SerializedItem::new("Terminal", 3, true)],
+                    false,
+                )),
+            ],
+        };
+        let serialized_workspace = SerializedWorkspace {
+            id: workspace.update(cx, |workspace, _| workspace.database_id()),
+            location: (["/root"]).into(),
+            center_group,
+            bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            zoomed_pane: None,
+            active_pane: None,
+            last_opened: None,
+        };
+        workspace
+            .update(cx, |_, cx| {
+                Workspace::load_workspace(serialized_workspace, Vec::new(), cx)
+            })
+            .await
+            .unwrap();
+
+        workspace.update(cx, |workspace, cx| {
+            let panes = workspace.center.panes();
+            assert_eq!(panes.len(), 1);
+            let items = panes[0].read(cx).items().collect::<Vec<_>>();
+            assert_eq!(items.len(), 1);
+            assert!(items[0].downcast::<TestItem>().is_some());
+        });
+    }
+
+    #[gpui::test]
+    async fn test_load_workspace_rescales_flexes_of_dropped_children(cx: &mut TestAppContext) {
+        use crate::persistence::{model::SerializedPaneGroup, SerializedAxis};
+
+        init_test(cx);
+        cx.update(register_deserializable_item::<TestItem>);
+
+        // The middle pane only holds a "Terminal", which can't be restored in this crate.
+        for (flexes, expected) in [
+            (vec![1., 2., 3.], vec![0.5, 1.5]),
+            (vec![0., 2., 0.], vec![1., 1.]),
+        ] {
+            let fs = FakeFs::new(cx.executor());
+            let project = Project::test(fs, [], cx).await;
+            let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+            let center_group = SerializedPaneGroup::Group {
+                axis: SerializedAxis(gpui::Axis::Horizontal),
+                flexes: Some(flexes),
+                children: vec![
+                    SerializedPaneGroup::Pane(SerializedPane::new(
+                        vec![SerializedItem::new("TestItem", 1, true)],
+                        true,
+                    )),
+                    SerializedPaneGroup::Pane(SerializedPane::new(
+                        vec![SerializedItem::new("Terminal", 2, true)],
+                        false,
+                    )),
+                    SerializedPaneGroup::Pane(SerializedPane::new(
+                        vec![SerializedItem::new("TestItem", 3, true)],
+                        false,
+                    )),
+                ],
+            };
+            let serialized_workspace = SerializedWorkspace {
+                id: workspace.update(cx, |workspace, _| workspace.database_id()),
+                location: (["/root"]).into(),
+                center_group,
+                bounds: Default::default(),
+                display: Default::default(),
+                docks: Default::default(),
+                zoomed_pane: None,
+                active_pane: None,
+                last_opened: None,
+            };
+            workspace
+                .update(cx, |_, cx| {
+                    Workspace::load_workspace(serialized_workspace, Vec::new(), cx)
+                })
+                .await
+                .unwrap();
+
+            workspace.update(cx, |workspace, _| {
+                let Member::Axis(axis) = &workspace.center.root else {
+                    panic!("expected the center to still be split");
+                };
+                assert_eq!(axis.members.len(), 2);
+                assert_eq!(*axis.flexes.lock(), expected);
+            });
+        }
+    }
+
+    #[gpui::test]
+    async fn test_load_workspace_zooms_pane_at_stored_path(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(register_deserializable_item::<TestItem>);
+
+        // The pane before the zoomed one fails to restore, shifting it to index 1
+        let (workspace, cx) =
+            load_center_group(three_panes_with_terminal(), Some(vec![2]), None, cx).await;
+        workspace.update(cx, |workspace, cx| {
+            let panes = workspace.center.panes();
+            assert_eq!(panes.len(), 2);
+            assert!(panes[1].read(cx).is_zoomed());
+            assert_eq!(workspace.zoomed, Some(panes[1].to_any().downgrade()));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_load_workspace_drops_zoom_of_unrestored_pane(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(register_deserializable_item::<TestItem>);
+
+        let (workspace, cx) =
+            load_center_group(three_panes_with_terminal(), Some(vec![1]), None, cx).await;
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.zoomed.is_none());
+            for pane in workspace.center.panes() {
+                assert!(!pane.read(cx).is_zoomed());
+            }
+        });
+    }
+
+    #[gpui::test]
+    async fn test_load_workspace_restores_active_pane(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(register_deserializable_item::<TestItem>);
+
+        // The pane before the active one fails to restore, shifting it to index 1
+        let (workspace, cx) =
+            load_center_group(three_panes_with_terminal(), None, Some(vec![2]), cx).await;
+        workspace.update(cx, |workspace, _| {
+            let panes = workspace.center.panes();
+            assert_eq!(panes.len(), 2);
+            assert_eq!(&workspace.active_pane, panes[1]);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_load_workspace_activates_first_pane_if_active_pane_is_dropped(
+        cx: &mut TestAppContext,
+    ) {
+        init_test(cx);
+        cx.update(register_deserializable_item::<TestItem>);
+
+        let (workspace, cx) =
+            load_center_group(three_panes_with_terminal(), None, Some(vec![1]), cx).await;
+        workspace.update(cx, |workspace, _| {
+            assert_eq!(workspace.active_pane, workspace.center.first_pane());
+            assert!(workspace.last_active_center_pane.is_none());
+        });
+    }
+
+    /// Three panes side by side, where the middle one only holds a "Terminal", which
+    /// nothing in this crate can restore.
+    fn three_panes_with_terminal() -> crate::persistence::model::SerializedPaneGroup {
+        use crate::persistence::{model::SerializedPaneGroup, SerializedAxis};
+
+        SerializedPaneGroup::Group {
+            axis: SerializedAxis(gpui::Axis::Horizontal),
+            flexes: None,
+            children: vec![
+                SerializedPaneGroup::Pane(SerializedPane::new(
+                    vec![SerializedItem::new("TestItem", 1, true)],
+                    false,
+                )),
+                SerializedPaneGroup::Pane(SerializedPane::new(
+                    vec![SerializedItem::new("Terminal", 2, true)],
+                    false,
+                )),
+                SerializedPaneGroup::Pane(SerializedPane::new(
+                    vec![SerializedItem::new("TestItem", 3, true)],
+                    false,
+                )),
+            ],
+        }
+    }
+
+    async fn load_center_group(
+        center_group: crate::persistence::model::SerializedPaneGroup,
+        zoomed_pane: Option<Vec<usize>>,
+        active_pane: Option<Vec<usize>>,
+        cx: &mut TestAppContext,
+    ) -> (View<Workspace>, &mut VisualTestContext) {
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        let serialized_workspace = SerializedWorkspace {
+            id: workspace.update(cx, |workspace, _| workspace.database_id()),
+            location: (["/root"]).into(),
+            center_group,
+            bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            zoomed_pane,
+            active_pane,
+            last_opened: None,
+        };
+        workspace
+            .update(cx, |_, cx| {
+                Workspace::load_workspace(serialized_workspace, Vec::new(), cx)
+            })
+            .await
+            .unwrap();
+        (workspace, cx)
+    }
+
     pub fn init_test(cx: &mut TestAppContext) {
         cx.update(|cx| {
             let settings_store = SettingsStore::test(cx);
@@ -1,13 +1,25 @@
+use gpui::{Axis, Modifiers};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::Settings;
 
+use crate::SplitDirection;
+
 #[derive(Deserialize)]
 pub struct WorkspaceSettings {
     pub active_pane_magnification: f32,
+    pub pane_split_handle_size: f32,
+    pub pane_gap: f32,
+    pub animate_pane_splits: bool,
+    pub resizable_panes: bool,
+    pub scroll_panes_on_overflow: bool,
+    pub pane_min_width: f32,
+    pub pane_min_height: f32,
     pub confirm_quit: bool,
     pub show_call_status_icon: bool,
     pub autosave: AutosaveSetting,
+    pub scroll_resize_modifier: ScrollResizeModifier,
+    pub new_pane_placement: NewPanePlacement,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -18,6 +30,38 @@ pub struct WorkspaceSettingsContent {
     ///
     /// Default: `1.0`
     pub active_pane_magnification: Option<f32>,
+    /// The size, in pixels, of the area around a split's divider that can be dragged
+    /// to resize the panes on either side of it.
+    ///
+    /// Default: `4.0`
+    pub pane_split_handle_size: Option<f32>,
+    /// The space, in pixels, left between split panes, with their divider drawn in the
+    /// middle of it.
+    ///
+    /// Default: `0.0`
+    pub pane_gap: Option<f32>,
+    /// Whether panes grow and shrink into place when a pane is split or closed, instead of
+    /// snapping to their new sizes.
+    ///
+    /// Default: true
+    pub animate_pane_splits: Option<bool>,
+    /// Whether the dividers between split panes can be dragged to resize them.
+    ///
+    /// Default: true
+    pub resizable_panes: Option<bool>,
+    /// Whether split panes that can't all fit at their minimum size can be scrolled
+    /// through, rather than being squeezed below it.
+    ///
+    /// Default: false
+    pub scroll_panes_on_overflow: Option<bool>,
+    /// The width, in pixels, below which dragging a vertical divider won't shrink a pane.
+    ///
+    /// Default: `80.0`
+    pub pane_min_width: Option<f32>,
+    /// The height, in pixels, below which dragging a horizontal divider won't shrink a pane.
+    ///
+    /// Default: `100.0`
+    pub pane_min_height: Option<f32>,
     /// Whether or not to prompt the user to confirm before closing the application.
     ///
     /// Default: false
@@ -30,6 +74,16 @@ pub struct WorkspaceSettingsContent {
     ///
     /// Default: off
     pub autosave: Option<AutosaveSetting>,
+    /// Which modifier key, held while scrolling over a split's divider, moves the divider
+    /// instead of scrolling the pane under it.
+    ///
+    /// Default: alt
+    pub scroll_resize_modifier: Option<ScrollResizeModifier>,
+    /// Which side of the split pane a pane created by a split command goes on. Pane
+    /// splits made by dragging onto an edge always use that edge.
+    ///
+    /// Default: direction
+    pub new_pane_placement: Option<NewPanePlacement>,
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
@@ -45,6 +99,55 @@ pub enum AutosaveSetting {
     OnWindowChange,
 }
 
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ScrollResizeModifier {
+    /// Never resize panes by scrolling.
+    Off,
+    Alt,
+    Control,
+    Shift,
+    /// Cmd on macOS, the super key elsewhere.
+    Platform,
+}
+
+impl ScrollResizeModifier {
+    pub fn is_held(self, modifiers: &Modifiers) -> bool {
+        match self {
+            ScrollResizeModifier::Off => false,
+            ScrollResizeModifier::Alt => modifiers.alt,
+            ScrollResizeModifier::Control => modifiers.control,
+            ScrollResizeModifier::Shift => modifiers.shift,
+            ScrollResizeModifier::Platform => modifiers.command,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NewPanePlacement {
+    /// Put the new pane on the side the split points to, e.g. on top when splitting up.
+    Direction,
+    /// Always put the new pane after the split one, i.e. below or to the right of it.
+    After,
+    /// Always put the new pane before the split one, i.e. above or to the left of it.
+    Before,
+}
+
+impl NewPanePlacement {
+    /// The direction to split in so that the new pane lands on this side, keeping the axis
+    /// of `direction`.
+    pub fn direction(self, direction: SplitDirection) -> SplitDirection {
+        match (self, direction.axis()) {
+            (NewPanePlacement::Direction, _) => direction,
+            (NewPanePlacement::After, Axis::Horizontal) => SplitDirection::Right,
+            (NewPanePlacement::After, Axis::Vertical) => SplitDirection::Down,
+            (NewPanePlacement::Before, Axis::Horizontal) => SplitDirection::Left,
+            (NewPanePlacement::Before, Axis::Vertical) => SplitDirection::Up,
+        }
+    }
+}
+
 impl Settings for WorkspaceSettings {
     const KEY: Option<&'static str> = None;
 